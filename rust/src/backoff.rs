@@ -0,0 +1,79 @@
+//! Capped exponential backoff for connection-oriented CLA reconnects.
+
+use ring::rand::{SecureRandom, SystemRandom};
+use std::time::Duration;
+
+/// A capped exponential backoff schedule: `initial * factor^attempt`, clamped to `max`, with
+/// optional jitter (the delay is scaled down to a random 50-100% of that value) so many peers
+/// reconnecting at once don't retry in lockstep.
+#[derive(Debug, Clone)]
+pub struct BackoffPolicy {
+    pub initial: Duration,
+    pub factor: f64,
+    pub max: Duration,
+    pub max_attempts: u32,
+    pub jitter: bool,
+}
+
+impl BackoffPolicy {
+    pub fn new(initial: Duration, factor: f64, max: Duration, max_attempts: u32) -> Self {
+        Self { initial, factor, max, max_attempts, jitter: false }
+    }
+
+    pub fn with_jitter(mut self) -> Self {
+        self.jitter = true;
+        self
+    }
+
+    /// Delay before the given 0-indexed attempt (0 = first retry after the initial failure).
+    pub fn delay_for_attempt(&self, attempt: u32) -> Duration {
+        let base = self.initial.as_secs_f64() * self.factor.powi(attempt as i32);
+        let capped = base.min(self.max.as_secs_f64()).max(0.0);
+
+        let delay_secs = if self.jitter {
+            capped * (0.5 + 0.5 * random_fraction())
+        } else {
+            capped
+        };
+
+        Duration::from_secs_f64(delay_secs)
+    }
+}
+
+/// A random value in `[0.0, 1.0]`, used for jitter. Falls back to the midpoint 0.5 (a fixed 75%
+/// delay) if the system RNG is unavailable.
+fn random_fraction() -> f64 {
+    let rng = SystemRandom::new();
+    let mut byte = [0u8; 2];
+    match rng.fill(&mut byte) {
+        Ok(()) => u16::from_be_bytes(byte) as f64 / u16::MAX as f64,
+        Err(_) => 0.5,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delay_grows_and_caps() {
+        let policy = BackoffPolicy::new(Duration::from_millis(100), 2.0, Duration::from_secs(30), 5);
+
+        assert_eq!(policy.delay_for_attempt(0), Duration::from_millis(100));
+        assert_eq!(policy.delay_for_attempt(1), Duration::from_millis(200));
+        assert_eq!(policy.delay_for_attempt(2), Duration::from_millis(400));
+        assert_eq!(policy.delay_for_attempt(20), Duration::from_secs(30));
+    }
+
+    #[test]
+    fn test_jitter_stays_within_bounds() {
+        let policy = BackoffPolicy::new(Duration::from_millis(100), 2.0, Duration::from_secs(30), 5).with_jitter();
+
+        for attempt in 0..5 {
+            let delay = policy.delay_for_attempt(attempt);
+            let base = Duration::from_millis(100).as_secs_f64() * 2f64.powi(attempt as i32);
+            assert!(delay.as_secs_f64() >= base * 0.5 - 0.001);
+            assert!(delay.as_secs_f64() <= base + 0.001);
+        }
+    }
+}
@@ -1,59 +1,132 @@
 use crate::{
+    backoff::BackoffPolicy,
+    codec::{self, codec_for, BundleCodec},
+    dns::{DnsResolver, SystemResolver},
     error::{BpError, BpResult},
-    types::TransportConfig,
+    peers::PeerManager,
+    routing::RoutingManager,
+    store::{BundleId, BundleStore, InMemoryBundleStore},
+    tcpcl::{SessionEvent, SessionState, TcpClMessage, TcpClSession},
+    tls::{self, MaybeTlsStream, TlsProfile},
+    types::{Bundle, Eid, TransportConfig},
 };
 use async_trait::async_trait;
 use bytes::Bytes;
+use chrono::Utc;
 use parking_lot::RwLock;
 use std::{
     collections::HashMap,
     fmt::Debug,
     net::SocketAddr,
-    sync::Arc,
+    sync::{atomic::{AtomicU32, Ordering}, Arc},
+    time::{Duration, Instant},
 };
 use tokio::{
-    net::{TcpListener, TcpStream, UdpSocket},
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream, UdpSocket, UnixListener, UnixStream},
     sync::mpsc,
 };
+use tokio_rustls::{TlsAcceptor, TlsConnector, TlsStream};
+use futures_util::{SinkExt, StreamExt};
+use tokio_tungstenite::{
+    tungstenite::{client::IntoClientRequest, Message},
+    WebSocketStream,
+};
+
+/// Transport protocols this SDK knows how to construct a CLA for, used to validate
+/// configuration profiles before a node starts.
+pub const KNOWN_PROTOCOLS: &[&str] = &["tcp", "udp", "ws", "wss", "quic", "tls", "unix"];
+
+/// Health of a registered CLA's own transport (listener/socket), as tracked by its
+/// background health-check/reconnect loop. Distinct from peer-level liveness
+/// ([`crate::peers::Liveness`]), which tracks individual remote nodes rather than this
+/// CLA's own ability to accept or originate traffic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionState {
+    /// Bound/listening (or, for send-only CLAs, never having failed) and able to carry
+    /// traffic.
+    Connected,
+    /// The underlying listener or socket died and a reconnect attempt is in backoff.
+    Reconnecting,
+    /// Reconnect attempts exhausted; this CLA needs to be restarted manually (e.g. via
+    /// `stop`/`start`).
+    Failed,
+}
 
 /// Convergence Layer Adapter trait for implementing transport protocols
 #[async_trait]
 pub trait Cla: Send + Sync + Debug {
     /// Get the protocol name
     fn protocol(&self) -> &str;
-    
+
     /// Get the local address
     fn local_address(&self) -> &str;
-    
+
     /// Get maximum payload size
     fn max_payload_size(&self) -> usize;
-    
+
     /// Start the CLA
     async fn start(&self) -> BpResult<()>;
-    
+
     /// Stop the CLA
     async fn stop(&self) -> BpResult<()>;
-    
+
     /// Send data to a remote address
     async fn send(&self, dest_addr: &str, data: Bytes) -> BpResult<()>;
-    
-    /// Set up bundle reception callback
+
+    /// Set up bundle reception callback. The `String` is the peer's negotiated EID where
+    /// the underlying protocol has one (e.g. [`TcpCla`]'s TCPCL contact handshake), falling
+    /// back to a socket address otherwise ([`UnixCla`] reports the peer's process credentials
+    /// instead), so a bundle agent can route by EID directly
+    /// instead of going back through the CLA to resolve one. The fuller negotiated session
+    /// parameters (keepalive interval, peer timeout) arrive separately via
+    /// [`TcpCla::on_session_event`]'s [`SessionEvent`], since those are per-session rather
+    /// than per-bundle.
     fn set_receive_callback(&self, callback: Arc<dyn Fn(Bytes, String) + Send + Sync>);
+
+    /// Current health of this CLA's own transport; see [`ConnectionState`]. Defaults to
+    /// always-`Connected` for CLAs (like [`WsCla`], which dials on demand rather than
+    /// holding a listener/socket that can fail independently) that don't run a
+    /// reconnect loop.
+    fn connection_state(&self) -> ConnectionState {
+        ConnectionState::Connected
+    }
+
+    /// Reconnect attempts made by this CLA's health check since it last reached
+    /// [`ConnectionState::Connected`].
+    fn retry_count(&self) -> u32 {
+        0
+    }
 }
 
 /// CLA manager for registering and managing transport protocols
 #[derive(Debug)]
 pub struct ClaManager {
     clas: RwLock<HashMap<String, Arc<dyn Cla>>>,
+    store: Arc<dyn BundleStore>,
+    peers: PeerManager,
+    /// Active routing engine consulted by `send_to_eid` to pick a next hop, if one has been
+    /// attached via `set_routing`; without it, `send_to_eid` addresses `dest_eid` directly.
+    routing: RwLock<Option<Arc<RoutingManager>>>,
 }
 
 impl ClaManager {
     pub fn new() -> Self {
         Self {
             clas: RwLock::new(HashMap::new()),
+            store: Arc::new(InMemoryBundleStore::new()),
+            peers: PeerManager::new(),
+            routing: RwLock::new(None),
         }
     }
 
+    /// Attach the routing engine `send_to_eid` should consult for a CGR-computed next hop,
+    /// instead of addressing `dest_eid` directly. Set by `NodeConfig::build` once a node's
+    /// `NodeHandle` has both a `ClaManager` and a `RoutingManager` to wire together.
+    pub fn set_routing(&self, routing: Arc<RoutingManager>) {
+        *self.routing.write() = Some(routing);
+    }
+
     /// Register a new CLA
     pub fn register(&self, cla: Arc<dyn Cla>) -> BpResult<()> {
         let mut clas = self.clas.write();
@@ -107,21 +180,182 @@ impl ClaManager {
         Ok(())
     }
 
-    /// Create and register a TCP CLA
-    pub async fn create_tcp_cla(&self, local_address: &str) -> BpResult<Arc<dyn Cla>> {
+    /// Create and register a TCP CLA. `local_address` may be a hostname (`host:port`) as
+    /// well as a literal `ip:port`; `resolver` is consulted to resolve it, both at startup
+    /// and again on every reconnect attempt so a rotated DNS record is picked up. The
+    /// background health check reconnects the listener under `retry_policy` if it ever
+    /// fails (see [`ConnectionState`]).
+    pub async fn create_tcp_cla(
+        &self,
+        local_address: &str,
+        retry_policy: BackoffPolicy,
+        resolver: Arc<dyn DnsResolver>,
+    ) -> BpResult<Arc<dyn Cla>> {
         let config = TransportConfig::tcp(local_address);
-        let cla = Arc::new(TcpCla::new(config)?) as Arc<dyn Cla>;
+        let cla = Arc::new(TcpCla::with_resolver(config, retry_policy, resolver)?) as Arc<dyn Cla>;
         self.register(cla.clone())?;
         Ok(cla)
     }
 
-    /// Create and register a UDP CLA
-    pub async fn create_udp_cla(&self, local_address: &str) -> BpResult<Arc<dyn Cla>> {
+    /// Create and register a UDP CLA. `local_address` may be a hostname (`host:port`) as
+    /// well as a literal `ip:port`; `resolver` is consulted to resolve it, both at startup
+    /// and again on every reconnect attempt so a rotated DNS record is picked up. The
+    /// background health check rebinds the socket under `retry_policy` if it ever fails
+    /// (see [`ConnectionState`]).
+    pub async fn create_udp_cla(
+        &self,
+        local_address: &str,
+        retry_policy: BackoffPolicy,
+        resolver: Arc<dyn DnsResolver>,
+    ) -> BpResult<Arc<dyn Cla>> {
         let config = TransportConfig::udp(local_address);
-        let cla = Arc::new(UdpCla::new(config)?) as Arc<dyn Cla>;
+        let cla = Arc::new(UdpCla::with_resolver(config, retry_policy, resolver)?) as Arc<dyn Cla>;
+        self.register(cla.clone())?;
+        Ok(cla)
+    }
+
+    /// Create and register a WebSocket CLA (`ws://`/`wss://` relay), for peers behind a
+    /// NAT/firewall that can only be reached via an outbound WebSocket connection.
+    pub async fn create_ws_cla(&self, config: TransportConfig) -> BpResult<Arc<dyn Cla>> {
+        let cla = Arc::new(WsCla::new(config)?) as Arc<dyn Cla>;
+        self.register(cla.clone())?;
+        Ok(cla)
+    }
+
+    /// Create and register a Unix-domain-socket CLA for co-located processes on the same
+    /// host. `local_path` is a filesystem path, not a `host:port`; a stale socket file left
+    /// behind by a crashed process is cleaned up on bind.
+    pub async fn create_unix_cla(&self, local_path: &str) -> BpResult<Arc<dyn Cla>> {
+        let config = TransportConfig::unix(local_path);
+        let cla = Arc::new(UnixCla::new(config)?) as Arc<dyn Cla>;
         self.register(cla.clone())?;
         Ok(cla)
     }
+
+    /// Create and register a TLS-secured TCP CLA, registered under protocol name `"tls"`
+    /// (see [`TransportConfig::tls`]) rather than sharing `"tcp"`'s name, so a node can run
+    /// both a plaintext and a TLS-only listener side by side. Otherwise identical to
+    /// [`Self::create_tcp_cla`]; `config` must carry `with_tls(...)` parameters.
+    pub async fn create_tls_cla(
+        &self,
+        config: TransportConfig,
+        retry_policy: BackoffPolicy,
+        resolver: Arc<dyn DnsResolver>,
+    ) -> BpResult<Arc<dyn Cla>> {
+        let cla = Arc::new(TcpCla::with_resolver(config, retry_policy, resolver)?) as Arc<dyn Cla>;
+        self.register(cla.clone())?;
+        Ok(cla)
+    }
+
+    /// Create and register a QUIC CLA. `local_address` may be a hostname (`host:port`) as
+    /// well as a literal `ip:port`; `resolver` is consulted to resolve it, both at startup
+    /// and again on every outbound dial. No health check runs for it: `retry_policy` is stored
+    /// but nothing rebinds the endpoint after it fails. `config` must already
+    /// carry `with_tls(...)` parameters, since QUIC has no cleartext mode.
+    pub async fn create_quic_cla(
+        &self,
+        config: TransportConfig,
+        retry_policy: BackoffPolicy,
+        resolver: Arc<dyn DnsResolver>,
+    ) -> BpResult<Arc<dyn Cla>> {
+        let cla = Arc::new(crate::quic::QuicCla::with_resolver(config, retry_policy, resolver)?) as Arc<dyn Cla>;
+        self.register(cla.clone())?;
+        Ok(cla)
+    }
+
+    /// The store backing `send_bundle`'s store-and-forward behavior.
+    pub fn store(&self) -> &Arc<dyn BundleStore> {
+        &self.store
+    }
+
+    /// The neighbor table backing `send_to_eid`'s CLA selection.
+    pub fn peers(&self) -> &PeerManager {
+        &self.peers
+    }
+
+    /// Send a bundle to `dest_eid` by picking one of its known reachable `(protocol,
+    /// address)` pairs from the [`PeerManager`] rather than requiring the caller to already
+    /// know which CLA/address to use. If a routing engine has been attached via
+    /// `set_routing`, it's consulted first for a CGR-computed next hop, and that next hop's
+    /// (rather than `dest_eid`'s) reachable CLAs are tried — so a multi-hop route actually
+    /// drives which peer this bundle is handed to next. Tries each reachable pair in order
+    /// until one succeeds; if none are reachable (or all fail), falls back to `send_bundle`'s
+    /// normal store-and-forward behavior so the bundle waits for the peer to come back online.
+    pub async fn send_to_eid(&self, dest_eid: &Eid, bundle: Bundle) -> BpResult<()> {
+        let next_hop = self.routing.read().as_ref()
+            .and_then(|routing| routing.find_route(dest_eid, bundle.payload_size(), Utc::now()))
+            .map(|route| route.next_hop)
+            .unwrap_or_else(|| dest_eid.clone());
+
+        let reachable = self.peers.reachable_clas(&next_hop);
+        let encoded = serde_json::to_vec(&bundle)
+            .map_err(|e| BpError::Protocol(format!("failed to encode bundle: {}", e)))?;
+
+        for (protocol, address) in &reachable {
+            if self.send(protocol, address, Bytes::from(encoded.clone())).await.is_ok() {
+                return Ok(());
+            }
+        }
+
+        // No reachable CLA delivered it; fall back to store-and-forward against the first
+        // known address (or, if the peer isn't known at all, a placeholder the retry loop
+        // can't act on until `add_peer` learns a real one).
+        let (protocol, address) = reachable
+            .into_iter()
+            .next()
+            .unwrap_or_else(|| ("unresolved".to_string(), next_hop.to_string()));
+        self.store.enqueue(bundle, protocol, address);
+        Ok(())
+    }
+
+    /// Send a bundle over `protocol` to `dest_addr`. If `dest_addr` is itself a `ws://`/
+    /// `wss://` URL, that scheme is used as the protocol regardless of `protocol`, since a
+    /// WS relay address is self-describing. If no CLA is registered for the resolved
+    /// protocol or the immediate send fails, the bundle is persisted in the [`BundleStore`]
+    /// instead of being dropped, to be retried by [`Self::retry_pending`] until it expires.
+    pub async fn send_bundle(&self, protocol: &str, dest_addr: &str, bundle: Bundle) -> BpResult<()> {
+        let protocol = Self::resolve_protocol(protocol, dest_addr);
+        let encoded = serde_json::to_vec(&bundle)
+            .map_err(|e| BpError::Protocol(format!("failed to encode bundle: {}", e)))?;
+
+        match self.send(protocol, dest_addr, Bytes::from(encoded)).await {
+            Ok(()) => Ok(()),
+            Err(_) => {
+                self.store.enqueue(bundle, protocol.to_string(), dest_addr.to_string());
+                Ok(())
+            }
+        }
+    }
+
+    /// Retry every store entry whose backoff has elapsed, and evict entries that expired
+    /// before being delivered. Returns the ids of bundles that expired this sweep so callers
+    /// can report them (e.g. as bundle-deletion status reports). Intended to be driven by a
+    /// periodic background task.
+    pub async fn retry_pending(&self) -> Vec<BundleId> {
+        let expired = self.store.sweep_expired();
+
+        for entry in self.store.due_for_retry() {
+            let id = entry.bundle.id;
+            let result = match serde_json::to_vec(&entry.bundle) {
+                Ok(encoded) => self.send(&entry.protocol, &entry.dest_addr, Bytes::from(encoded)).await,
+                Err(e) => Err(BpError::Protocol(format!("failed to encode bundle: {}", e))),
+            };
+            self.store.record_attempt(id, result.is_ok());
+        }
+
+        expired
+    }
+
+    /// `dest_addr`'s `ws://`/`wss://` scheme, if it has one, else `protocol` unchanged.
+    fn resolve_protocol<'a>(protocol: &'a str, dest_addr: &str) -> &'a str {
+        if dest_addr.starts_with("wss://") {
+            "wss"
+        } else if dest_addr.starts_with("ws://") {
+            "ws"
+        } else {
+            protocol
+        }
+    }
 }
 
 impl Default for ClaManager {
@@ -130,75 +364,494 @@ impl Default for ClaManager {
     }
 }
 
+/// A live, handshaken TCPCL connection held in `TcpCla`'s write pool.
+struct PooledConnection {
+    stream: MaybeTlsStream,
+    session: TcpClSession,
+}
+
 /// TCP-based Convergence Layer Adapter
 pub struct TcpCla {
     config: TransportConfig,
+    codec: Arc<dyn BundleCodec>,
+    tls_profile: Option<TlsProfile>,
     listener: RwLock<Option<TcpListener>>,
     receive_callback: RwLock<Option<Arc<dyn Fn(Bytes, String) + Send + Sync>>>,
+    /// Invoked whenever a TCPCL session (inbound or pooled outbound) comes up or goes
+    /// down; see [`Self::on_session_event`].
+    session_callback: RwLock<Option<Arc<dyn Fn(SessionEvent) + Send + Sync>>>,
     shutdown_tx: RwLock<Option<mpsc::Sender<()>>>,
+    /// Live outbound connections keyed by peer address, reused across sends and dropped on
+    /// write failure so the next send reconnects instead of writing into a dead socket.
+    pool: RwLock<HashMap<String, Arc<tokio::sync::Mutex<PooledConnection>>>>,
+    /// When each pooled connection last sent a bundle, tracked separately from `pool` so
+    /// `get_connection` can check staleness without taking the per-connection async lock;
+    /// see `config.pool_idle_timeout`/`config.pool_max_size`.
+    pool_last_used: RwLock<HashMap<String, Instant>>,
+    /// Health of the bound listener; written by the `accept_loop`'s health check, read by
+    /// [`Cla::connection_state`]. `Arc`-wrapped so the spawned loop shares the same cell
+    /// `self` reads from rather than a private copy.
+    conn_state: Arc<RwLock<ConnectionState>>,
+    /// Reconnect attempts since the listener last came back up; see
+    /// [`Cla::retry_count`].
+    retry_count: Arc<AtomicU32>,
+    /// Backoff schedule for rebinding the listener after it fails; see
+    /// [`Self::with_retry_policy`].
+    retry_policy: BackoffPolicy,
+    /// Invoked before each reconnect attempt, so a caller can drive SDK-level
+    /// statistics; see [`Self::on_reconnect_attempt`].
+    reconnect_hook: RwLock<Option<Arc<dyn Fn() + Send + Sync>>>,
+    /// Invoked after each completed bundle transfer (`true` for sent, `false` for received),
+    /// with the transfer's encoded byte length; see [`Self::on_bundle_transfer`].
+    transfer_hook: RwLock<Option<Arc<dyn Fn(bool, u64) + Send + Sync>>>,
+    /// Resolves `config.local_address`/outbound `dest_addr`s that aren't already literal
+    /// socket addresses; see [`crate::dns::DnsResolver`].
+    resolver: Arc<dyn DnsResolver>,
 }
 
 impl Debug for TcpCla {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("TcpCla")
             .field("config", &self.config)
+            .field("codec", &self.codec.name())
+            .field("tls", &self.tls_profile.is_some())
             .field("listener", &self.listener)
             .field("receive_callback", &"<callback>")
+            .field("session_callback", &"<callback>")
+            .field("transfer_hook", &"<callback>")
             .field("shutdown_tx", &self.shutdown_tx)
+            .field("pool", &"<connections>")
+            .field("pool_last_used", &self.pool_last_used)
+            .field("conn_state", &self.conn_state)
+            .field("retry_count", &self.retry_count.load(Ordering::Relaxed))
+            .field("resolver", &self.resolver)
             .finish()
     }
 }
 
+/// Default backoff for a CLA's own listener/socket health check, used by [`TcpCla::new`]/
+/// [`UdpCla::new`] (and [`crate::quic::QuicCla::new`]) when a caller doesn't care to tune it
+/// via `with_retry_policy`.
+pub(crate) fn default_retry_policy() -> BackoffPolicy {
+    BackoffPolicy::new(Duration::from_secs(1), 2.0, Duration::from_secs(60), 5).with_jitter()
+}
+
+/// Fill in `SessionEvent::Up`'s `peer_certificate` from `stream`, so a bundle agent's
+/// `on_session_event` callback can authorize a sender by its TLS identity instead of just its
+/// EID. A no-op for `SessionEvent::Down` or a plain (non-TLS) stream.
+fn with_peer_certificate(event: SessionEvent, stream: &MaybeTlsStream) -> SessionEvent {
+    match event {
+        SessionEvent::Up { peer_eid, keepalive_interval, peer_timeout, .. } => SessionEvent::Up {
+            peer_eid,
+            keepalive_interval,
+            peer_timeout,
+            peer_certificate: stream.peer_certificate().map(|cert| Bytes::from(cert.0)),
+        },
+        down @ SessionEvent::Down { .. } => down,
+    }
+}
+
 impl TcpCla {
     pub fn new(config: TransportConfig) -> BpResult<Self> {
-        if config.protocol != "tcp" {
+        Self::with_retry_policy(config, default_retry_policy())
+    }
+
+    /// Like [`Self::new`], but with a caller-supplied backoff schedule for reconnecting
+    /// the listener after it fails (see [`ConnectionState`]).
+    pub fn with_retry_policy(config: TransportConfig, retry_policy: BackoffPolicy) -> BpResult<Self> {
+        Self::with_resolver(config, retry_policy, Arc::new(SystemResolver))
+    }
+
+    /// Like [`Self::with_retry_policy`], but with a caller-supplied [`DnsResolver`] for
+    /// `config.local_address`/outbound `dest_addr`s that aren't literal socket addresses.
+    pub fn with_resolver(
+        config: TransportConfig,
+        retry_policy: BackoffPolicy,
+        resolver: Arc<dyn DnsResolver>,
+    ) -> BpResult<Self> {
+        if config.protocol != "tcp" && config.protocol != "tls" {
             return Err(BpError::InvalidArgs);
         }
 
+        let codec = codec_for(&config.codec)?;
+        let tls_profile = TlsProfile::from_config(&config);
+        if config.protocol == "tls" && tls_profile.is_none() {
+            return Err(BpError::TlsHandshake("\"tls\" protocol requires with_tls(...) configuration".to_string()));
+        }
+
         Ok(Self {
             config,
+            codec,
+            tls_profile,
             listener: RwLock::new(None),
             receive_callback: RwLock::new(None),
+            session_callback: RwLock::new(None),
             shutdown_tx: RwLock::new(None),
+            pool: RwLock::new(HashMap::new()),
+            pool_last_used: RwLock::new(HashMap::new()),
+            conn_state: Arc::new(RwLock::new(ConnectionState::Connected)),
+            retry_count: Arc::new(AtomicU32::new(0)),
+            retry_policy,
+            reconnect_hook: RwLock::new(None),
+            transfer_hook: RwLock::new(None),
+            resolver,
         })
     }
 
+    /// Register a callback invoked whenever a TCPCL session is established or torn down.
+    /// `SessionEvent::Up` carries the keepalive interval and idle timeout negotiated with
+    /// that peer during the contact handshake, plus the peer's leaf TLS certificate if this
+    /// is a `"tls"`-protocol CLA (or `"tcp"` with `with_tls` set), so a bundle agent can
+    /// authorize senders by certificate rather than trusting the claimed EID alone.
+    pub fn on_session_event(&self, callback: impl Fn(SessionEvent) + Send + Sync + 'static) {
+        *self.session_callback.write() = Some(Arc::new(callback));
+    }
+
+    /// Register a callback invoked before each listener reconnect attempt, so a caller
+    /// can drive SDK-level statistics (e.g. `Statistics::reconnect_attempts`) without this
+    /// module needing to know about `BpSdk`.
+    pub fn on_reconnect_attempt(&self, callback: impl Fn() + Send + Sync + 'static) {
+        *self.reconnect_hook.write() = Some(Arc::new(callback));
+    }
+
+    /// Register a callback invoked after each completed bundle transfer, so a caller can drive
+    /// SDK-level statistics (e.g. `BpSdk::record_bundle_sent`/`record_bundle_received`) from
+    /// this CLA's I/O path without it needing to know about `BpSdk`. The callback receives
+    /// `true` for an outbound transfer or `false` for an inbound one, and the transfer's
+    /// encoded byte length.
+    pub fn on_bundle_transfer(&self, callback: impl Fn(bool, u64) + Send + Sync + 'static) {
+        *self.transfer_hook.write() = Some(Arc::new(callback));
+    }
+
+    fn emit_session_event(&self, event: SessionEvent) {
+        if let Some(callback) = self.session_callback.read().as_ref().cloned() {
+            callback(event);
+        }
+    }
+
+    /// Re-resolve `local_address` and rebind a fresh listener, with capped exponential
+    /// backoff, incrementing `retry_count` and firing `reconnect_hook` before each
+    /// attempt. Re-resolving on every attempt (rather than reusing the address from the
+    /// last successful bind) means a hostname whose DNS record rotated is picked up.
+    /// Returns `None` once `retry_policy.max_attempts` is exhausted.
+    async fn reconnect_listener(
+        local_address: &str,
+        resolver: &Arc<dyn DnsResolver>,
+        retry_policy: &BackoffPolicy,
+        retry_count: &Arc<AtomicU32>,
+        reconnect_hook: &Option<Arc<dyn Fn() + Send + Sync>>,
+    ) -> Option<TcpListener> {
+        for attempt in 0..retry_policy.max_attempts.max(1) {
+            tokio::time::sleep(retry_policy.delay_for_attempt(attempt)).await;
+            retry_count.fetch_add(1, Ordering::Relaxed);
+            if let Some(hook) = reconnect_hook {
+                hook();
+            }
+            let addr = match resolver.resolve(local_address).await {
+                Ok(addr) => addr,
+                Err(_) => continue,
+            };
+            if let Ok(listener) = TcpListener::bind(addr).await {
+                return Some(listener);
+            }
+        }
+        None
+    }
+
+    /// Reuse a pooled connection to `dest_addr` if one is live and hasn't sat idle past
+    /// `config.pool_idle_timeout`, otherwise dial and handshake a new one with capped
+    /// exponential backoff, pooling it for future sends (evicting the least-recently-used
+    /// entry first if that would put the pool over `config.pool_max_size`).
+    async fn get_connection(&self, dest_addr: &str) -> BpResult<Arc<tokio::sync::Mutex<PooledConnection>>> {
+        let pooled_conn = self.pool.read().get(dest_addr).cloned();
+        if let Some(conn) = pooled_conn {
+            let stale = self
+                .pool_last_used
+                .read()
+                .get(dest_addr)
+                .is_some_and(|last_used| last_used.elapsed() > self.config.pool_idle_timeout);
+            if !stale {
+                return Ok(conn);
+            }
+            self.evict_connection(dest_addr).await;
+        }
+
+        let (pooled, event) = self.connect_with_backoff(dest_addr).await?;
+        let keepalive_interval = match &event {
+            SessionEvent::Up { keepalive_interval, .. } => *keepalive_interval,
+            SessionEvent::Down { .. } => unreachable!("connect_handshake only returns SessionEvent::Up"),
+        };
+        self.emit_session_event(event);
+
+        self.evict_lru_if_full(dest_addr);
+
+        let conn = Arc::new(tokio::sync::Mutex::new(pooled));
+        self.pool.write().insert(dest_addr.to_string(), conn.clone());
+        self.pool_last_used.write().insert(dest_addr.to_string(), Instant::now());
+        self.spawn_pooled_keepalive(conn.clone(), keepalive_interval);
+        Ok(conn)
+    }
+
+    /// Close and drop the pooled connection to `dest_addr`, if any, notifying the session
+    /// callback that it went down.
+    async fn evict_connection(&self, dest_addr: &str) {
+        let conn = self.pool.write().remove(dest_addr);
+        self.pool_last_used.write().remove(dest_addr);
+        if let Some(conn) = conn {
+            let mut guard = conn.lock().await;
+            let _ = guard.session.close(&mut guard.stream).await;
+            self.emit_session_event(SessionEvent::Down { peer_eid: guard.session.peer_eid.clone() });
+        }
+    }
+
+    /// If adding one more entry would put the pool over `config.pool_max_size`, drop the
+    /// least-recently-used entry (other than `keep`, the destination about to be inserted).
+    fn evict_lru_if_full(&self, keep: &str) {
+        if self.pool.read().len() < self.config.pool_max_size {
+            return;
+        }
+        let oldest = self
+            .pool_last_used
+            .read()
+            .iter()
+            .filter(|(addr, _)| addr.as_str() != keep)
+            .min_by_key(|(_, last_used)| **last_used)
+            .map(|(addr, _)| addr.clone());
+        if let Some(addr) = oldest {
+            self.pool.write().remove(&addr);
+            self.pool_last_used.write().remove(&addr);
+        }
+    }
+
+    /// Periodically lock `conn` just long enough to write a KEEPALIVE, at the negotiated
+    /// cadence, stopping once the session is no longer established (e.g. evicted by
+    /// [`Self::send`] after a write failure).
+    fn spawn_pooled_keepalive(&self, conn: Arc<tokio::sync::Mutex<PooledConnection>>, keepalive_interval: u16) {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(Duration::from_secs(keepalive_interval.max(1) as u64));
+            ticker.tick().await; // first tick fires immediately; skip it
+            loop {
+                ticker.tick().await;
+                let mut guard = conn.lock().await;
+                if guard.session.state != SessionState::SessionEstablished {
+                    break;
+                }
+                if guard.session.send_keepalive(&mut guard.stream).await.is_err() {
+                    break;
+                }
+            }
+        });
+    }
+
+    /// Re-resolves `dest_addr` on every attempt (not just the first), so a hostname whose
+    /// DNS record rotated between retries is picked up rather than retrying a stale IP.
+    async fn connect_with_backoff(&self, dest_addr: &str) -> BpResult<(PooledConnection, SessionEvent)> {
+        let policy = BackoffPolicy::new(
+            self.config.reconnect_initial_backoff,
+            self.config.reconnect_backoff_factor,
+            self.config.reconnect_max_backoff,
+            self.config.reconnect_max_attempts,
+        )
+        .with_jitter();
+
+        let mut last_err = BpError::Protocol("reconnect attempts exhausted".to_string());
+        for attempt in 0..policy.max_attempts.max(1) {
+            if attempt > 0 {
+                tokio::time::sleep(policy.delay_for_attempt(attempt - 1)).await;
+            }
+            let addr = match self.resolver.resolve(dest_addr).await {
+                Ok(addr) => addr,
+                Err(e) => {
+                    last_err = e;
+                    continue;
+                }
+            };
+            match self.try_connect(addr).await {
+                Ok(conn) => return Ok(conn),
+                Err(e) => last_err = e,
+            }
+        }
+        Err(last_err)
+    }
+
+    async fn try_connect(&self, addr: SocketAddr) -> BpResult<(PooledConnection, SessionEvent)> {
+        let tcp_stream = TcpStream::connect(addr)
+            .await
+            .map_err(|_| BpError::Protocol("Failed to connect".to_string()))?;
+
+        let mut stream = match &self.tls_profile {
+            Some(profile) => {
+                let connector = tls::build_connector(profile)?;
+                let server_name_str = profile.server_name.clone().unwrap_or_else(|| addr.ip().to_string());
+                let server_name = rustls::ServerName::try_from(server_name_str.as_str())
+                    .map_err(|e| BpError::TlsHandshake(format!("invalid peer server name: {}", e)))?;
+                let tls_stream = connector
+                    .connect(server_name, tcp_stream)
+                    .await
+                    .map_err(|e| BpError::TlsHandshake(e.to_string()))?;
+                MaybeTlsStream::tls(TlsStream::Client(tls_stream))
+            }
+            None => MaybeTlsStream::plain(tcp_stream),
+        };
+
+        let mut session = TcpClSession::with_peer_timeout(
+            self.local_eid(),
+            self.keepalive_interval(),
+            self.peer_timeout(),
+            self.config.max_payload_size,
+        );
+        let event = with_peer_certificate(session.connect_handshake(&mut stream).await?, &stream);
+
+        Ok((PooledConnection { stream, session }, event))
+    }
+
+    /// Encode `bundle` with this CLA's configured codec and send it over a TCPCL session to
+    /// `dest_addr`; segmentation and framing is handled by [`TcpClSession`].
+    pub async fn send_bundle(&self, dest_addr: &str, bundle: &Bundle) -> BpResult<()> {
+        let encoded = self.codec.encode(bundle)?;
+        self.send(dest_addr, encoded).await
+    }
+
+    /// Decode a reassembled TCPCL transfer with this CLA's configured codec.
+    pub fn deserialize_bundle(&self, data: &[u8]) -> BpResult<Bundle> {
+        self.codec.decode(data)
+    }
+
+    #[allow(clippy::too_many_arguments)]
     async fn handle_connection(
         stream: TcpStream,
         peer_addr: SocketAddr,
+        local_eid: String,
+        keepalive_interval: u16,
+        peer_timeout: u16,
+        max_segment_size: usize,
+        tls_acceptor: Option<TlsAcceptor>,
         callback: Arc<dyn Fn(Bytes, String) + Send + Sync>,
+        session_callback: Option<Arc<dyn Fn(SessionEvent) + Send + Sync>>,
+        transfer_hook: Option<Arc<dyn Fn(bool, u64) + Send + Sync>>,
     ) {
-        use tokio::io::AsyncReadExt;
-        
-        let mut stream = stream;
-        let mut buffer = vec![0u8; 65536];
-        
-        while let Ok(n) = stream.read(&mut buffer).await {
-            if n == 0 { break; }
-            let data = Bytes::from(buffer[..n].to_vec());
-            callback(data, peer_addr.to_string());
+        let mut stream = match tls_acceptor {
+            Some(acceptor) => match acceptor.accept(stream).await {
+                Ok(tls_stream) => MaybeTlsStream::tls(TlsStream::Server(tls_stream)),
+                Err(_) => return,
+            },
+            None => MaybeTlsStream::plain(stream),
+        };
+
+        let mut session =
+            TcpClSession::with_peer_timeout(local_eid, keepalive_interval, peer_timeout, max_segment_size);
+        let event = match session.accept_handshake(&mut stream).await {
+            Ok(event) => with_peer_certificate(event, &stream),
+            Err(_) => return,
+        };
+        let negotiated_keepalive = session.keepalive_interval;
+        if let Some(cb) = &session_callback {
+            cb(event);
+        }
+
+        let mut keepalive_ticker = tokio::time::interval(Duration::from_secs(negotiated_keepalive.max(1) as u64));
+        keepalive_ticker.tick().await; // first tick fires immediately; skip it
+
+        loop {
+            tokio::select! {
+                _ = keepalive_ticker.tick() => {
+                    if session.send_keepalive(&mut stream).await.is_err() {
+                        break;
+                    }
+                }
+                message = session.recv_message_with_timeout(&mut stream) => {
+                    match message {
+                        Ok(TcpClMessage::Bundle(data)) => {
+                            let peer = session.peer_eid.clone().unwrap_or_else(|| peer_addr.to_string());
+                            if let Some(hook) = &transfer_hook {
+                                hook(false, data.len() as u64);
+                            }
+                            callback(data, peer);
+                        }
+                        Ok(TcpClMessage::Keepalive) => continue,
+                        Ok(TcpClMessage::SessTerm) | Err(_) => break,
+                    }
+                }
+            }
+        }
+
+        if let Some(cb) = &session_callback {
+            cb(SessionEvent::Down { peer_eid: session.peer_eid.clone() });
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     async fn accept_loop(
-        listener: TcpListener,
+        mut listener: TcpListener,
+        local_address: String,
+        resolver: Arc<dyn DnsResolver>,
+        local_eid: String,
+        keepalive_interval: u16,
+        peer_timeout: u16,
+        max_segment_size: usize,
+        tls_acceptor: Option<TlsAcceptor>,
         callback: Arc<dyn Fn(Bytes, String) + Send + Sync>,
+        session_callback: Option<Arc<dyn Fn(SessionEvent) + Send + Sync>>,
+        transfer_hook: Option<Arc<dyn Fn(bool, u64) + Send + Sync>>,
+        conn_state: Arc<RwLock<ConnectionState>>,
+        retry_count: Arc<AtomicU32>,
+        retry_policy: BackoffPolicy,
+        reconnect_hook: Option<Arc<dyn Fn() + Send + Sync>>,
         mut shutdown_rx: mpsc::Receiver<()>,
     ) {
         loop {
             tokio::select! {
                 result = listener.accept() => {
-                    if let Ok((stream, peer_addr)) = result {
-                        let callback_clone = callback.clone();
-                        tokio::spawn(Self::handle_connection(stream, peer_addr, callback_clone));
-                    } else {
-                        break;
+                    match result {
+                        Ok((stream, peer_addr)) => {
+                            let callback_clone = callback.clone();
+                            let session_callback_clone = session_callback.clone();
+                            let transfer_hook_clone = transfer_hook.clone();
+                            let local_eid = local_eid.clone();
+                            let tls_acceptor = tls_acceptor.clone();
+                            tokio::spawn(Self::handle_connection(
+                                stream, peer_addr, local_eid, keepalive_interval, peer_timeout, max_segment_size,
+                                tls_acceptor, callback_clone, session_callback_clone, transfer_hook_clone,
+                            ));
+                        }
+                        Err(_) => {
+                            *conn_state.write() = ConnectionState::Reconnecting;
+                            match Self::reconnect_listener(&local_address, &resolver, &retry_policy, &retry_count, &reconnect_hook).await {
+                                Some(new_listener) => {
+                                    listener = new_listener;
+                                    retry_count.store(0, Ordering::Relaxed);
+                                    *conn_state.write() = ConnectionState::Connected;
+                                }
+                                None => {
+                                    *conn_state.write() = ConnectionState::Failed;
+                                    break;
+                                }
+                            }
+                        }
                     }
                 }
                 _ = shutdown_rx.recv() => break,
             }
         }
     }
+
+    /// This CLA's own node EID for the TCPCL contact header, taken from
+    /// `config.parameters["local_eid"]` if set.
+    fn local_eid(&self) -> String {
+        self.config.parameters.get("local_eid").cloned().unwrap_or_else(|| self.config.local_address.clone())
+    }
+
+    /// Keepalive interval (seconds) this side proposes during TCPCL contact negotiation;
+    /// see [`TransportConfig::keepalive_interval`].
+    fn keepalive_interval(&self) -> u16 {
+        self.config.keepalive_interval
+    }
+
+    /// Idle timeout (seconds) this side proposes during TCPCL contact negotiation; see
+    /// [`TransportConfig::peer_timeout`].
+    fn peer_timeout(&self) -> u16 {
+        self.config.peer_timeout
+    }
 }
 
 #[async_trait]
@@ -216,27 +869,49 @@ impl Cla for TcpCla {
     }
 
     async fn start(&self) -> BpResult<()> {
-        let addr: SocketAddr = self.config.local_address
-            .parse()
-            .map_err(|_| BpError::InvalidArgs)?;
+        let addr = self.resolver.resolve(&self.config.local_address).await?;
 
         let listener = TcpListener::bind(addr)
             .await
             .map_err(|_| BpError::Protocol("Failed to bind TCP listener".to_string()))?;
 
         *self.listener.write() = Some(listener);
+        *self.conn_state.write() = ConnectionState::Connected;
+        self.retry_count.store(0, Ordering::Relaxed);
 
         let callback = self.receive_callback.read().as_ref().cloned();
         if let Some(callback) = callback {
             let (shutdown_tx, shutdown_rx) = mpsc::channel(1);
-            
+
             *self.shutdown_tx.write() = Some(shutdown_tx);
-            
+
             let new_listener = TcpListener::bind(addr)
                 .await
                 .map_err(|_| BpError::Protocol("Failed to rebind listener".to_string()))?;
-            
-            tokio::spawn(Self::accept_loop(new_listener, callback, shutdown_rx));
+
+            let tls_acceptor = self.tls_profile.as_ref().map(tls::build_acceptor).transpose()?;
+            let session_callback = self.session_callback.read().as_ref().cloned();
+            let reconnect_hook = self.reconnect_hook.read().as_ref().cloned();
+            let transfer_hook = self.transfer_hook.read().as_ref().cloned();
+
+            tokio::spawn(Self::accept_loop(
+                new_listener,
+                self.config.local_address.clone(),
+                self.resolver.clone(),
+                self.local_eid(),
+                self.keepalive_interval(),
+                self.peer_timeout(),
+                self.config.max_payload_size,
+                tls_acceptor,
+                callback,
+                session_callback,
+                transfer_hook,
+                self.conn_state.clone(),
+                self.retry_count.clone(),
+                self.retry_policy.clone(),
+                reconnect_hook,
+                shutdown_rx,
+            ));
         }
 
         Ok(())
@@ -247,80 +922,209 @@ impl Cla for TcpCla {
         if let Some(tx) = shutdown_tx {
             let _ = tx.send(()).await;
         }
-        
+
         *self.listener.write() = None;
+        let pooled: Vec<_> = self.pool.write().drain().map(|(_, conn)| conn).collect();
+        self.pool_last_used.write().clear();
+        for conn in pooled {
+            let mut guard = conn.lock().await;
+            let _ = guard.session.close(&mut guard.stream).await;
+        }
         Ok(())
     }
 
     async fn send(&self, dest_addr: &str, data: Bytes) -> BpResult<()> {
-        use tokio::io::AsyncWriteExt;
-        
-        let addr: SocketAddr = dest_addr.parse().map_err(|_| BpError::InvalidArgs)?;
-
-        let mut stream = TcpStream::connect(addr)
-            .await
-            .map_err(|_| BpError::Protocol("Failed to connect".to_string()))?;
-
-        stream.write_all(&data)
-            .await
-            .map_err(|_| BpError::Protocol("Failed to send data".to_string()))?;
+        let conn = self.get_connection(dest_addr).await?;
+        let mut guard = conn.lock().await;
+        let result = guard.session.send_bundle(&mut guard.stream, &data).await;
+        let peer_eid = guard.session.peer_eid.clone();
+        drop(guard);
 
-        Ok(())
+        if result.is_err() {
+            self.pool.write().remove(dest_addr);
+            self.pool_last_used.write().remove(dest_addr);
+            self.emit_session_event(SessionEvent::Down { peer_eid });
+        } else {
+            self.pool_last_used.write().insert(dest_addr.to_string(), Instant::now());
+            if let Some(hook) = self.transfer_hook.read().as_ref() {
+                hook(true, data.len() as u64);
+            }
+        }
+        result
     }
 
     fn set_receive_callback(&self, callback: Arc<dyn Fn(Bytes, String) + Send + Sync>) {
         *self.receive_callback.write() = Some(callback);
     }
+
+    fn connection_state(&self) -> ConnectionState {
+        *self.conn_state.read()
+    }
+
+    fn retry_count(&self) -> u32 {
+        self.retry_count.load(Ordering::Relaxed)
+    }
 }
 
 /// UDP-based Convergence Layer Adapter
 pub struct UdpCla {
     config: TransportConfig,
-    socket: RwLock<Option<Arc<UdpSocket>>>,
+    codec: Arc<dyn BundleCodec>,
+    socket: Arc<RwLock<Option<Arc<UdpSocket>>>>,
     receive_callback: RwLock<Option<Arc<dyn Fn(Bytes, String) + Send + Sync>>>,
     shutdown_tx: RwLock<Option<mpsc::Sender<()>>>,
+    /// Health of the bound socket; written by the `receive_loop`'s health check, read by
+    /// [`Cla::connection_state`].
+    conn_state: Arc<RwLock<ConnectionState>>,
+    /// Reconnect attempts since the socket last came back up; see [`Cla::retry_count`].
+    retry_count: Arc<AtomicU32>,
+    /// Backoff schedule for rebinding the socket after it fails; see
+    /// [`Self::with_retry_policy`].
+    retry_policy: BackoffPolicy,
+    /// Invoked before each reconnect attempt; see [`TcpCla::on_reconnect_attempt`].
+    reconnect_hook: RwLock<Option<Arc<dyn Fn() + Send + Sync>>>,
+    /// Resolves `config.local_address`/outbound `dest_addr`s that aren't already literal
+    /// socket addresses; see [`crate::dns::DnsResolver`].
+    resolver: Arc<dyn DnsResolver>,
 }
 
 impl Debug for UdpCla {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("UdpCla")
             .field("config", &self.config)
+            .field("codec", &self.codec.name())
             .field("socket", &self.socket)
             .field("receive_callback", &"<callback>")
             .field("shutdown_tx", &self.shutdown_tx)
+            .field("conn_state", &self.conn_state)
+            .field("retry_count", &self.retry_count.load(Ordering::Relaxed))
+            .field("resolver", &self.resolver)
             .finish()
     }
 }
 
 impl UdpCla {
     pub fn new(config: TransportConfig) -> BpResult<Self> {
+        Self::with_retry_policy(config, default_retry_policy())
+    }
+
+    /// Like [`Self::new`], but with a caller-supplied backoff schedule for rebinding the
+    /// socket after it fails (see [`ConnectionState`]).
+    pub fn with_retry_policy(config: TransportConfig, retry_policy: BackoffPolicy) -> BpResult<Self> {
+        Self::with_resolver(config, retry_policy, Arc::new(SystemResolver))
+    }
+
+    /// Like [`Self::with_retry_policy`], but with a caller-supplied [`DnsResolver`] for
+    /// `config.local_address`/outbound `dest_addr`s that aren't literal socket addresses.
+    pub fn with_resolver(
+        config: TransportConfig,
+        retry_policy: BackoffPolicy,
+        resolver: Arc<dyn DnsResolver>,
+    ) -> BpResult<Self> {
         if config.protocol != "udp" {
             return Err(BpError::InvalidArgs);
         }
 
+        let codec = codec_for(&config.codec)?;
+
         Ok(Self {
             config,
-            socket: RwLock::new(None),
+            codec,
+            socket: Arc::new(RwLock::new(None)),
             receive_callback: RwLock::new(None),
             shutdown_tx: RwLock::new(None),
+            conn_state: Arc::new(RwLock::new(ConnectionState::Connected)),
+            retry_count: Arc::new(AtomicU32::new(0)),
+            retry_policy,
+            reconnect_hook: RwLock::new(None),
+            resolver,
         })
     }
 
+    /// Register a callback invoked before each socket reconnect attempt; see
+    /// [`TcpCla::on_reconnect_attempt`].
+    pub fn on_reconnect_attempt(&self, callback: impl Fn() + Send + Sync + 'static) {
+        *self.reconnect_hook.write() = Some(Arc::new(callback));
+    }
+
+    /// Re-resolves `local_address` and rebinds a fresh socket, with capped exponential
+    /// backoff, incrementing `retry_count` and firing `reconnect_hook` before each
+    /// attempt. Returns `None` once `retry_policy.max_attempts` is exhausted.
+    async fn reconnect_socket(
+        local_address: &str,
+        resolver: &Arc<dyn DnsResolver>,
+        retry_policy: &BackoffPolicy,
+        retry_count: &Arc<AtomicU32>,
+        reconnect_hook: &Option<Arc<dyn Fn() + Send + Sync>>,
+    ) -> Option<Arc<UdpSocket>> {
+        for attempt in 0..retry_policy.max_attempts.max(1) {
+            tokio::time::sleep(retry_policy.delay_for_attempt(attempt)).await;
+            retry_count.fetch_add(1, Ordering::Relaxed);
+            if let Some(hook) = reconnect_hook {
+                hook();
+            }
+            let addr = match resolver.resolve(local_address).await {
+                Ok(addr) => addr,
+                Err(_) => continue,
+            };
+            if let Ok(socket) = UdpSocket::bind(addr).await {
+                return Some(Arc::new(socket));
+            }
+        }
+        None
+    }
+
+    /// Encode `bundle` with this CLA's configured codec and send it, length-prefixed, to
+    /// `dest_addr`.
+    pub async fn send_bundle(&self, dest_addr: &str, bundle: &Bundle) -> BpResult<()> {
+        let encoded = self.codec.encode(bundle)?;
+        self.send(dest_addr, codec::frame(&encoded)).await
+    }
+
+    /// Strip the length prefix from data received off the wire and decode it with this
+    /// CLA's configured codec.
+    pub fn deserialize_bundle(&self, data: &[u8]) -> BpResult<Bundle> {
+        self.codec.decode(codec::unframe(data)?)
+    }
+
+    #[allow(clippy::too_many_arguments)]
     async fn receive_loop(
-        socket: Arc<UdpSocket>,
+        mut socket: Arc<UdpSocket>,
         callback: Arc<dyn Fn(Bytes, String) + Send + Sync>,
+        local_address: String,
+        resolver: Arc<dyn DnsResolver>,
+        shared_socket: Arc<RwLock<Option<Arc<UdpSocket>>>>,
+        conn_state: Arc<RwLock<ConnectionState>>,
+        retry_count: Arc<AtomicU32>,
+        retry_policy: BackoffPolicy,
+        reconnect_hook: Option<Arc<dyn Fn() + Send + Sync>>,
         mut shutdown_rx: mpsc::Receiver<()>,
     ) {
         let mut buffer = vec![0u8; 65536];
-        
+
         loop {
             tokio::select! {
                 result = socket.recv_from(&mut buffer) => {
-                    if let Ok((len, peer_addr)) = result {
-                        let data = Bytes::from(buffer[..len].to_vec());
-                        callback(data, peer_addr.to_string());
-                    } else {
-                        break;
+                    match result {
+                        Ok((len, peer_addr)) => {
+                            let data = Bytes::from(buffer[..len].to_vec());
+                            callback(data, peer_addr.to_string());
+                        }
+                        Err(_) => {
+                            *conn_state.write() = ConnectionState::Reconnecting;
+                            match Self::reconnect_socket(&local_address, &resolver, &retry_policy, &retry_count, &reconnect_hook).await {
+                                Some(new_socket) => {
+                                    socket = new_socket.clone();
+                                    *shared_socket.write() = Some(new_socket);
+                                    retry_count.store(0, Ordering::Relaxed);
+                                    *conn_state.write() = ConnectionState::Connected;
+                                }
+                                None => {
+                                    *conn_state.write() = ConnectionState::Failed;
+                                    break;
+                                }
+                            }
+                        }
                     }
                 }
                 _ = shutdown_rx.recv() => break,
@@ -344,9 +1148,7 @@ impl Cla for UdpCla {
     }
 
     async fn start(&self) -> BpResult<()> {
-        let addr: SocketAddr = self.config.local_address
-            .parse()
-            .map_err(|_| BpError::InvalidArgs)?;
+        let addr = self.resolver.resolve(&self.config.local_address).await?;
 
         let socket = UdpSocket::bind(addr)
             .await
@@ -354,14 +1156,28 @@ impl Cla for UdpCla {
 
         let socket = Arc::new(socket);
         *self.socket.write() = Some(socket.clone());
+        *self.conn_state.write() = ConnectionState::Connected;
+        self.retry_count.store(0, Ordering::Relaxed);
 
         let callback = self.receive_callback.read().as_ref().cloned();
         if let Some(callback) = callback {
             let (shutdown_tx, shutdown_rx) = mpsc::channel(1);
-            
+
             *self.shutdown_tx.write() = Some(shutdown_tx);
-            
-            tokio::spawn(Self::receive_loop(socket, callback, shutdown_rx));
+            let reconnect_hook = self.reconnect_hook.read().as_ref().cloned();
+
+            tokio::spawn(Self::receive_loop(
+                socket,
+                callback,
+                self.config.local_address.clone(),
+                self.resolver.clone(),
+                self.socket.clone(),
+                self.conn_state.clone(),
+                self.retry_count.clone(),
+                self.retry_policy.clone(),
+                reconnect_hook,
+                shutdown_rx,
+            ));
         }
 
         Ok(())
@@ -383,7 +1199,7 @@ impl Cla for UdpCla {
             .ok_or(BpError::NotInitialized)?
             .clone();
 
-        let addr: SocketAddr = dest_addr.parse().map_err(|_| BpError::InvalidArgs)?;
+        let addr = self.resolver.resolve(dest_addr).await?;
 
         socket.send_to(&data, addr)
             .await
@@ -395,4 +1211,499 @@ impl Cla for UdpCla {
     fn set_receive_callback(&self, callback: Arc<dyn Fn(Bytes, String) + Send + Sync>) {
         *self.receive_callback.write() = Some(callback);
     }
-} 
\ No newline at end of file
+
+    fn connection_state(&self) -> ConnectionState {
+        *self.conn_state.read()
+    }
+
+    fn retry_count(&self) -> u32 {
+        self.retry_count.load(Ordering::Relaxed)
+    }
+}
+
+/// WebSocket-based Convergence Layer Adapter, for peers behind a NAT/firewall that can only
+/// reach a relay via an outbound `ws://`/`wss://` connection. A bundle is one binary WS
+/// message; frame boundaries make the length-prefix framing the other CLAs need unnecessary.
+/// The HTTP path lives directly in the `ws://`/`wss://` URL passed to [`Self::send_bundle`]/
+/// [`Cla::send`] rather than as a separate `TransportConfig` field, since `dest_addr` is
+/// already a full URL; `wss` reuses the same `tls_profile`/rustls setup as the TLS TCP CLA.
+pub struct WsCla {
+    config: TransportConfig,
+    codec: Arc<dyn BundleCodec>,
+    tls_profile: Option<TlsProfile>,
+    listener: RwLock<Option<TcpListener>>,
+    connections: RwLock<HashMap<String, Arc<tokio::sync::Mutex<WebSocketStream<MaybeTlsStream>>>>>,
+    receive_callback: RwLock<Option<Arc<dyn Fn(Bytes, String) + Send + Sync>>>,
+    shutdown_tx: RwLock<Option<mpsc::Sender<()>>>,
+}
+
+impl Debug for WsCla {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WsCla")
+            .field("config", &self.config)
+            .field("codec", &self.codec.name())
+            .field("tls", &self.tls_profile.is_some())
+            .field("listener", &self.listener)
+            .field("connections", &"<connections>")
+            .field("receive_callback", &"<callback>")
+            .field("shutdown_tx", &self.shutdown_tx)
+            .finish()
+    }
+}
+
+impl WsCla {
+    pub fn new(config: TransportConfig) -> BpResult<Self> {
+        if config.protocol != "ws" && config.protocol != "wss" {
+            return Err(BpError::InvalidArgs);
+        }
+
+        let codec = codec_for(&config.codec)?;
+        let tls_profile = TlsProfile::from_config(&config);
+
+        Ok(Self {
+            config,
+            codec,
+            tls_profile,
+            listener: RwLock::new(None),
+            connections: RwLock::new(HashMap::new()),
+            receive_callback: RwLock::new(None),
+            shutdown_tx: RwLock::new(None),
+        })
+    }
+
+    /// Encode `bundle` with this CLA's configured codec and send it as one binary WS message
+    /// to `relay_url` (a `ws://`/`wss://` URL).
+    pub async fn send_bundle(&self, relay_url: &str, bundle: &Bundle) -> BpResult<()> {
+        let encoded = self.codec.encode(bundle)?;
+        self.send(relay_url, encoded).await
+    }
+
+    /// Decode a received binary WS message with this CLA's configured codec.
+    pub fn deserialize_bundle(&self, data: &[u8]) -> BpResult<Bundle> {
+        self.codec.decode(data)
+    }
+
+    /// Dial `url` (a `ws://`/`wss://` relay address), performing our own TCP connect plus
+    /// optional TLS wrap so `wss` reuses the same cert config as the TLS TCP CLA, then the
+    /// WebSocket client handshake over that stream.
+    async fn dial(&self, url: &str) -> BpResult<WebSocketStream<MaybeTlsStream>> {
+        let parsed = url::Url::parse(url).map_err(|_| BpError::InvalidArgs)?;
+        let is_wss = parsed.scheme() == "wss";
+        let host = parsed.host_str().ok_or(BpError::InvalidArgs)?;
+        let port = parsed.port_or_known_default().unwrap_or(if is_wss { 443 } else { 80 });
+
+        let tcp_stream = TcpStream::connect((host, port))
+            .await
+            .map_err(|_| BpError::Protocol("Failed to connect to WS relay".to_string()))?;
+
+        let stream = if is_wss {
+            let profile = self
+                .tls_profile
+                .as_ref()
+                .ok_or_else(|| BpError::TlsHandshake("wss requires with_tls(...) configuration".to_string()))?;
+            let connector = tls::build_connector(profile)?;
+            let server_name = rustls::ServerName::try_from(host)
+                .map_err(|e| BpError::TlsHandshake(format!("invalid relay host: {}", e)))?;
+            let tls_stream = connector
+                .connect(server_name, tcp_stream)
+                .await
+                .map_err(|e| BpError::TlsHandshake(e.to_string()))?;
+            MaybeTlsStream::tls(TlsStream::Client(tls_stream))
+        } else {
+            MaybeTlsStream::plain(tcp_stream)
+        };
+
+        let request = url
+            .into_client_request()
+            .map_err(|e| BpError::Protocol(format!("invalid WS relay URL: {}", e)))?;
+
+        let (ws_stream, _response) = tokio_tungstenite::client_async(request, stream)
+            .await
+            .map_err(|e| BpError::Protocol(format!("WS handshake failed: {}", e)))?;
+
+        Ok(ws_stream)
+    }
+
+    async fn get_connection(&self, url: &str) -> BpResult<Arc<tokio::sync::Mutex<WebSocketStream<MaybeTlsStream>>>> {
+        if let Some(conn) = self.connections.read().get(url).cloned() {
+            return Ok(conn);
+        }
+
+        let conn = Arc::new(tokio::sync::Mutex::new(self.dial(url).await?));
+        self.connections.write().insert(url.to_string(), conn.clone());
+        Ok(conn)
+    }
+
+    async fn handle_incoming(
+        mut ws_stream: WebSocketStream<MaybeTlsStream>,
+        peer_addr: String,
+        callback: Arc<dyn Fn(Bytes, String) + Send + Sync>,
+    ) {
+        while let Some(Ok(message)) = ws_stream.next().await {
+            if let Message::Binary(data) = message {
+                callback(Bytes::from(data), peer_addr.clone());
+            }
+        }
+    }
+
+    async fn accept_loop(
+        listener: TcpListener,
+        tls_acceptor: Option<TlsAcceptor>,
+        callback: Arc<dyn Fn(Bytes, String) + Send + Sync>,
+        mut shutdown_rx: mpsc::Receiver<()>,
+    ) {
+        loop {
+            tokio::select! {
+                result = listener.accept() => {
+                    if let Ok((stream, peer_addr)) = result {
+                        let callback_clone = callback.clone();
+                        let tls_acceptor = tls_acceptor.clone();
+                        tokio::spawn(async move {
+                            let stream = match tls_acceptor {
+                                Some(acceptor) => match acceptor.accept(stream).await {
+                                    Ok(tls_stream) => MaybeTlsStream::tls(TlsStream::Server(tls_stream)),
+                                    Err(_) => return,
+                                },
+                                None => MaybeTlsStream::plain(stream),
+                            };
+                            if let Ok(ws_stream) = tokio_tungstenite::accept_async(stream).await {
+                                Self::handle_incoming(ws_stream, peer_addr.to_string(), callback_clone).await;
+                            }
+                        });
+                    } else {
+                        break;
+                    }
+                }
+                _ = shutdown_rx.recv() => break,
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Cla for WsCla {
+    fn protocol(&self) -> &str {
+        &self.config.protocol
+    }
+
+    fn local_address(&self) -> &str {
+        &self.config.local_address
+    }
+
+    fn max_payload_size(&self) -> usize {
+        self.config.max_payload_size
+    }
+
+    async fn start(&self) -> BpResult<()> {
+        if self.config.local_address.is_empty() {
+            return Ok(());
+        }
+
+        let addr: SocketAddr = self.config.local_address.parse().map_err(|_| BpError::InvalidArgs)?;
+
+        let listener = TcpListener::bind(addr)
+            .await
+            .map_err(|_| BpError::Protocol("Failed to bind WS listener".to_string()))?;
+        *self.listener.write() = Some(listener);
+
+        let callback = self.receive_callback.read().as_ref().cloned();
+        if let Some(callback) = callback {
+            let (shutdown_tx, shutdown_rx) = mpsc::channel(1);
+            *self.shutdown_tx.write() = Some(shutdown_tx);
+
+            let new_listener = TcpListener::bind(addr)
+                .await
+                .map_err(|_| BpError::Protocol("Failed to rebind WS listener".to_string()))?;
+
+            let tls_acceptor = self.tls_profile.as_ref().map(tls::build_acceptor).transpose()?;
+            tokio::spawn(Self::accept_loop(new_listener, tls_acceptor, callback, shutdown_rx));
+        }
+
+        Ok(())
+    }
+
+    async fn stop(&self) -> BpResult<()> {
+        let shutdown_tx = self.shutdown_tx.write().take();
+        if let Some(tx) = shutdown_tx {
+            let _ = tx.send(()).await;
+        }
+
+        *self.listener.write() = None;
+        self.connections.write().clear();
+        Ok(())
+    }
+
+    async fn send(&self, dest_addr: &str, data: Bytes) -> BpResult<()> {
+        let conn = self.get_connection(dest_addr).await?;
+        let mut guard = conn.lock().await;
+        let result = guard
+            .send(Message::Binary(data.to_vec()))
+            .await
+            .map_err(|e| BpError::Protocol(format!("WS send failed: {}", e)));
+        drop(guard);
+
+        if result.is_err() {
+            self.connections.write().remove(dest_addr);
+        }
+        result
+    }
+
+    fn set_receive_callback(&self, callback: Arc<dyn Fn(Bytes, String) + Send + Sync>) {
+        *self.receive_callback.write() = Some(callback);
+    }
+}
+
+/// Format a Unix socket peer's credentials (as reported by `SO_PEERCRED`) the same way a
+/// network CLA reports a socket address, so a receive callback can authorize a sender by
+/// PID/UID without this module needing a richer identity type.
+fn format_peer_cred(cred: &tokio::net::unix::UCred) -> String {
+    match cred.pid() {
+        Some(pid) => format!("pid={},uid={},gid={}", pid, cred.uid(), cred.gid()),
+        None => format!("uid={},gid={}", cred.uid(), cred.gid()),
+    }
+}
+
+/// Unix-domain-socket Convergence Layer Adapter, for bundle transfer between co-located
+/// processes on the same host (e.g. a routing daemon and a local application) without the
+/// three-way handshake and kernel overhead of a loopback TCP/UDP round trip. `local_address`
+/// is a filesystem path rather than a `host:port`. Reuses the length-prefix framing `UdpCla`
+/// uses (`codec::frame`/`codec::unframe`), since a Unix stream, unlike a WS connection, has
+/// no message boundaries of its own.
+pub struct UnixCla {
+    config: TransportConfig,
+    codec: Arc<dyn BundleCodec>,
+    listener: RwLock<Option<UnixListener>>,
+    receive_callback: RwLock<Option<Arc<dyn Fn(Bytes, String) + Send + Sync>>>,
+    shutdown_tx: RwLock<Option<mpsc::Sender<()>>>,
+}
+
+impl Debug for UnixCla {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UnixCla")
+            .field("config", &self.config)
+            .field("codec", &self.codec.name())
+            .field("listener", &self.listener)
+            .field("receive_callback", &"<callback>")
+            .field("shutdown_tx", &self.shutdown_tx)
+            .finish()
+    }
+}
+
+impl UnixCla {
+    pub fn new(config: TransportConfig) -> BpResult<Self> {
+        if config.protocol != "unix" {
+            return Err(BpError::InvalidArgs);
+        }
+
+        let codec = codec_for(&config.codec)?;
+
+        Ok(Self {
+            config,
+            codec,
+            listener: RwLock::new(None),
+            receive_callback: RwLock::new(None),
+            shutdown_tx: RwLock::new(None),
+        })
+    }
+
+    /// Encode `bundle` with this CLA's configured codec and send it, length-prefixed, to the
+    /// socket at `dest_path`.
+    pub async fn send_bundle(&self, dest_path: &str, bundle: &Bundle) -> BpResult<()> {
+        let encoded = self.codec.encode(bundle)?;
+        self.send(dest_path, codec::frame(&encoded)).await
+    }
+
+    /// Strip the length prefix from data received off the socket and decode it with this
+    /// CLA's configured codec.
+    pub fn deserialize_bundle(&self, data: &[u8]) -> BpResult<Bundle> {
+        self.codec.decode(codec::unframe(data)?)
+    }
+
+    /// Read length-prefixed frames from `stream`, handing each to `callback` along with the
+    /// sender's credentials, until the peer closes the connection or a read fails.
+    async fn handle_connection(
+        mut stream: UnixStream,
+        peer: String,
+        callback: Arc<dyn Fn(Bytes, String) + Send + Sync>,
+        max_payload_size: usize,
+    ) {
+        loop {
+            let mut len_buf = [0u8; 4];
+            if stream.read_exact(&mut len_buf).await.is_err() {
+                break;
+            }
+            let len = u32::from_be_bytes(len_buf) as usize;
+            if len > max_payload_size {
+                break;
+            }
+
+            let mut body = vec![0u8; len];
+            if stream.read_exact(&mut body).await.is_err() {
+                break;
+            }
+
+            let mut framed = Vec::with_capacity(4 + len);
+            framed.extend_from_slice(&len_buf);
+            framed.extend_from_slice(&body);
+            callback(Bytes::from(framed), peer.clone());
+        }
+    }
+
+    async fn accept_loop(
+        listener: UnixListener,
+        callback: Arc<dyn Fn(Bytes, String) + Send + Sync>,
+        max_payload_size: usize,
+        mut shutdown_rx: mpsc::Receiver<()>,
+    ) {
+        loop {
+            tokio::select! {
+                result = listener.accept() => {
+                    let Ok((stream, _addr)) = result else { break };
+                    let peer = match stream.peer_cred() {
+                        Ok(cred) => format_peer_cred(&cred),
+                        Err(_) => "unknown".to_string(),
+                    };
+                    let callback_clone = callback.clone();
+                    tokio::spawn(Self::handle_connection(stream, peer, callback_clone, max_payload_size));
+                }
+                _ = shutdown_rx.recv() => break,
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Cla for UnixCla {
+    fn protocol(&self) -> &str {
+        &self.config.protocol
+    }
+
+    fn local_address(&self) -> &str {
+        &self.config.local_address
+    }
+
+    fn max_payload_size(&self) -> usize {
+        self.config.max_payload_size
+    }
+
+    async fn start(&self) -> BpResult<()> {
+        // A process that crashed without closing its listener leaves the socket file
+        // behind; `bind` would otherwise fail with `AddrInUse` forever.
+        let _ = std::fs::remove_file(&self.config.local_address);
+
+        let listener = UnixListener::bind(&self.config.local_address)
+            .map_err(|_| BpError::Protocol("Failed to bind Unix listener".to_string()))?;
+
+        let callback = self.receive_callback.read().as_ref().cloned();
+        if let Some(callback) = callback {
+            let (shutdown_tx, shutdown_rx) = mpsc::channel(1);
+            *self.shutdown_tx.write() = Some(shutdown_tx);
+            tokio::spawn(Self::accept_loop(listener, callback, self.config.max_payload_size, shutdown_rx));
+        } else {
+            *self.listener.write() = Some(listener);
+        }
+
+        Ok(())
+    }
+
+    async fn stop(&self) -> BpResult<()> {
+        let shutdown_tx = self.shutdown_tx.write().take();
+        if let Some(tx) = shutdown_tx {
+            let _ = tx.send(()).await;
+        }
+
+        *self.listener.write() = None;
+        let _ = std::fs::remove_file(&self.config.local_address);
+        Ok(())
+    }
+
+    async fn send(&self, dest_addr: &str, data: Bytes) -> BpResult<()> {
+        let mut stream = UnixStream::connect(dest_addr)
+            .await
+            .map_err(|_| BpError::Protocol("Failed to connect to Unix socket".to_string()))?;
+
+        stream
+            .write_all(&data)
+            .await
+            .map_err(|_| BpError::Protocol("Failed to write to Unix socket".to_string()))?;
+
+        Ok(())
+    }
+
+    fn set_receive_callback(&self, callback: Arc<dyn Fn(Bytes, String) + Send + Sync>) {
+        *self.receive_callback.write() = Some(callback);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::routing::ContactGraphRouting;
+    use crate::types::Contact;
+
+    #[tokio::test]
+    async fn test_send_to_eid_addresses_cgr_next_hop_not_final_dest() {
+        let relay = Eid::new("ipn:9.1").unwrap();
+        let dest = Eid::new("ipn:9.2").unwrap();
+        let now = Utc::now();
+
+        // Two-hop contact plan: the local node ("ipn:0.0", `ContactGraphRouting`'s implicit
+        // source) can only reach `dest` via `relay`.
+        let to_relay = Contact::new(relay.clone(), now - chrono::Duration::minutes(1), now + chrono::Duration::hours(1), 1_000_000);
+        let relay_to_dest = Contact::new(dest.clone(), now - chrono::Duration::minutes(1), now + chrono::Duration::hours(1), 1_000_000)
+            .with_from_eid(relay.clone());
+
+        let routing = Arc::new(RoutingManager::new());
+        routing.register_engine(Arc::new(ContactGraphRouting::new(vec![to_relay, relay_to_dest], Vec::new())));
+        routing.set_active_engine("contact_graph").unwrap();
+
+        let manager = ClaManager::new();
+        manager.set_routing(routing);
+        // No CLA is registered for "tcp", so the send attempt fails and the bundle falls
+        // through to store-and-forward; what matters is which address it's addressed to.
+        manager.peers().add_peer(relay.clone(), "tcp", "127.0.0.1:9001");
+        manager.peers().add_peer(dest.clone(), "tcp", "127.0.0.1:9002");
+
+        let bundle = Bundle::new(Eid::new("ipn:0.0").unwrap(), dest.clone(), "routed payload");
+        manager.send_to_eid(&dest, bundle).await.unwrap();
+
+        let pending = manager.store().due_for_retry();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].dest_addr, "127.0.0.1:9001");
+    }
+
+    #[tokio::test]
+    async fn test_pool_evicts_connection_after_idle_timeout() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    let mut stream = MaybeTlsStream::plain(stream);
+                    let mut session = TcpClSession::new("ipn:2.1", 30, 65536);
+                    if session.accept_handshake(&mut stream).await.is_ok() {
+                        tokio::time::sleep(Duration::from_secs(2)).await;
+                    }
+                });
+            }
+        });
+
+        let config = TransportConfig::tcp("127.0.0.1:0").with_pool_idle_timeout(Duration::from_millis(20));
+        let cla = TcpCla::new(config).unwrap();
+        let dest = addr.to_string();
+
+        let first = cla.get_connection(&dest).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        // Regression test: a stale pooled connection used to deadlock here forever, because
+        // the read guard from the pool lookup was still held when eviction tried to take the
+        // write lock on the same `RwLock`. This must complete rather than hang.
+        let second = tokio::time::timeout(Duration::from_secs(2), cla.get_connection(&dest))
+            .await
+            .expect("get_connection deadlocked on a stale pooled connection")
+            .unwrap();
+
+        assert!(!Arc::ptr_eq(&first, &second));
+    }
+}
@@ -0,0 +1,188 @@
+//! Pluggable wire formats for serializing [`Bundle`]s over a [`Cla`](crate::cla::Cla).
+//!
+//! CLAs previously hardwired JSON. [`BundleCodec`] lets a transport pick JSON, canonical
+//! CBOR, bincode, or MessagePack instead, selected via [`TransportConfig::codec`].
+
+use crate::{
+    error::{BpError, BpResult},
+    types::Bundle,
+};
+use bytes::Bytes;
+use std::sync::Arc;
+
+/// Encodes and decodes [`Bundle`]s for the wire. Implementations must round-trip a bundle
+/// exactly; framing (the 4-byte length prefix CLAs use) is handled above this trait.
+pub trait BundleCodec: Send + Sync + std::fmt::Debug {
+    /// Name this codec is selected by in [`TransportConfig::codec`].
+    fn name(&self) -> &str;
+
+    /// Encode a bundle to its wire representation.
+    fn encode(&self, bundle: &Bundle) -> BpResult<Bytes>;
+
+    /// Decode a bundle from its wire representation.
+    fn decode(&self, data: &[u8]) -> BpResult<Bundle>;
+}
+
+/// The SDK's original format: `serde_json`.
+#[derive(Debug, Default)]
+pub struct JsonCodec;
+
+impl BundleCodec for JsonCodec {
+    fn name(&self) -> &str {
+        "json"
+    }
+
+    fn encode(&self, bundle: &Bundle) -> BpResult<Bytes> {
+        let bytes = serde_json::to_vec(bundle)
+            .map_err(|e| BpError::Protocol(format!("JSON encode failed: {}", e)))?;
+        Ok(Bytes::from(bytes))
+    }
+
+    fn decode(&self, data: &[u8]) -> BpResult<Bundle> {
+        serde_json::from_slice(data)
+            .map_err(|e| BpError::Protocol(format!("JSON decode failed: {}", e)))
+    }
+}
+
+/// Canonical deterministic CBOR in the BPv7 block layout with one trailing CRC-32C (no
+/// per-block CRC values), delegating to [`Bundle::to_cbor`] and [`Bundle::from_cbor`].
+#[derive(Debug, Default)]
+pub struct CborCodec;
+
+impl BundleCodec for CborCodec {
+    fn name(&self) -> &str {
+        "cbor"
+    }
+
+    fn encode(&self, bundle: &Bundle) -> BpResult<Bytes> {
+        bundle.to_cbor()
+    }
+
+    fn decode(&self, data: &[u8]) -> BpResult<Bundle> {
+        Bundle::from_cbor(data)
+    }
+}
+
+/// Compact binary format via `bincode`.
+#[derive(Debug, Default)]
+pub struct BincodeCodec;
+
+impl BundleCodec for BincodeCodec {
+    fn name(&self) -> &str {
+        "bincode"
+    }
+
+    fn encode(&self, bundle: &Bundle) -> BpResult<Bytes> {
+        let bytes = bincode::serialize(bundle)
+            .map_err(|e| BpError::Protocol(format!("bincode encode failed: {}", e)))?;
+        Ok(Bytes::from(bytes))
+    }
+
+    fn decode(&self, data: &[u8]) -> BpResult<Bundle> {
+        bincode::deserialize(data)
+            .map_err(|e| BpError::Protocol(format!("bincode decode failed: {}", e)))
+    }
+}
+
+/// MessagePack via `rmp-serde`, for interop with non-Rust DTN peers that prefer it over CBOR.
+#[derive(Debug, Default)]
+pub struct MsgPackCodec;
+
+impl BundleCodec for MsgPackCodec {
+    fn name(&self) -> &str {
+        "msgpack"
+    }
+
+    fn encode(&self, bundle: &Bundle) -> BpResult<Bytes> {
+        let bytes = rmp_serde::to_vec(bundle)
+            .map_err(|e| BpError::Protocol(format!("MessagePack encode failed: {}", e)))?;
+        Ok(Bytes::from(bytes))
+    }
+
+    fn decode(&self, data: &[u8]) -> BpResult<Bundle> {
+        rmp_serde::from_slice(data)
+            .map_err(|e| BpError::Protocol(format!("MessagePack decode failed: {}", e)))
+    }
+}
+
+/// Look up a codec by the name used in [`TransportConfig::codec`](crate::types::TransportConfig::codec).
+pub fn codec_for(name: &str) -> BpResult<Arc<dyn BundleCodec>> {
+    match name {
+        "json" => Ok(Arc::new(JsonCodec)),
+        "cbor" => Ok(Arc::new(CborCodec)),
+        "bincode" => Ok(Arc::new(BincodeCodec)),
+        "msgpack" => Ok(Arc::new(MsgPackCodec)),
+        _ => Err(BpError::InvalidArgs),
+    }
+}
+
+/// Prepend a 4-byte big-endian length prefix, shared by every codec so framing stays
+/// uniform regardless of which wire format is in use.
+pub fn frame(payload: &Bytes) -> Bytes {
+    let mut out = Vec::with_capacity(4 + payload.len());
+    out.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+    out.extend_from_slice(payload);
+    Bytes::from(out)
+}
+
+/// Strip and validate the 4-byte length prefix added by [`frame`], returning the remaining
+/// payload bytes.
+pub fn unframe(data: &[u8]) -> BpResult<&[u8]> {
+    if data.len() < 4 {
+        return Err(BpError::Protocol("frame too short for length prefix".to_string()));
+    }
+    let len = u32::from_be_bytes([data[0], data[1], data[2], data[3]]) as usize;
+    let body = &data[4..];
+    if body.len() != len {
+        return Err(BpError::Protocol("frame length prefix does not match body".to_string()));
+    }
+    Ok(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Eid;
+
+    fn sample_bundle() -> Bundle {
+        Bundle::new(
+            Eid::new("ipn:1.1").unwrap(),
+            Eid::new("ipn:2.1").unwrap(),
+            "codec test payload",
+        )
+    }
+
+    #[test]
+    fn test_all_codecs_round_trip() {
+        for name in ["json", "cbor", "bincode", "msgpack"] {
+            let codec = codec_for(name).unwrap();
+            let bundle = sample_bundle();
+
+            let encoded = codec.encode(&bundle).unwrap();
+            let decoded = codec.decode(&encoded).unwrap();
+
+            assert_eq!(decoded.source_eid, bundle.source_eid);
+            assert_eq!(decoded.dest_eid, bundle.dest_eid);
+            assert_eq!(decoded.payload, bundle.payload);
+        }
+    }
+
+    #[test]
+    fn test_unknown_codec_rejected() {
+        assert!(codec_for("carrier-pigeon").is_err());
+    }
+
+    #[test]
+    fn test_frame_round_trip() {
+        let payload = Bytes::from_static(b"hello");
+        let framed = frame(&payload);
+        assert_eq!(unframe(&framed).unwrap(), &payload[..]);
+    }
+
+    #[test]
+    fn test_unframe_rejects_mismatched_length() {
+        let mut framed = frame(&Bytes::from_static(b"hello")).to_vec();
+        framed.push(0xFF);
+        assert!(unframe(&framed).is_err());
+    }
+}
@@ -0,0 +1,314 @@
+use crate::{
+    bpsec::{BpsecManager, TrustMode},
+    cla::{ClaManager, KNOWN_PROTOCOLS},
+    core::BpSdk,
+    error::{BpError, BpResult},
+    routing::RoutingManager,
+    types::{Bundle, Eid, Route, TransportConfig},
+};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+use std::sync::Arc;
+
+/// One transport a node listens on or dials out over, as written in a node's YAML profile.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransportProfile {
+    pub protocol: String,
+    pub listen_address: String,
+    /// Address peers behind a NAT should be told to reach this node on, if different
+    /// from `listen_address`.
+    pub advertised_address: Option<String>,
+}
+
+/// BPSec trust bootstrap, as written in a node's YAML profile.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SecurityProfile {
+    /// "shared-secret" or "explicit"; absent means BPSec is not configured.
+    pub trust_mode: Option<String>,
+    /// Passphrase for shared-secret mode. Explicit mode does not read this value: `build`
+    /// gives the node a freshly generated random identity keypair.
+    pub identity_secret: Option<String>,
+    /// Paths to raw trusted peer public keys (explicit mode).
+    pub trusted_key_paths: Vec<String>,
+}
+
+/// Declarative description of a node: identity, transports, routing choice, and BPSec trust,
+/// loadable from YAML or collected interactively via [`NodeConfig::wizard`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeConfig {
+    pub node_eid: String,
+    pub transports: Vec<TransportProfile>,
+    pub routing_engine: String,
+    #[serde(default)]
+    pub security: SecurityProfile,
+}
+
+/// A node built from a [`NodeConfig`]: the SDK instance plus its installed subsystems.
+/// `routing` is `Arc`-wrapped so `clas` can share it (see `NodeConfig::build`) and have
+/// `ClaManager::send_to_eid` consult the same CGR-computed routes as `route_for`.
+pub struct NodeHandle {
+    pub sdk: BpSdk,
+    pub clas: ClaManager,
+    pub routing: Arc<RoutingManager>,
+    pub security: BpsecManager,
+}
+
+impl NodeHandle {
+    /// OpenMetrics/Prometheus text exposition combining the SDK's bundle counters with the
+    /// active routing engine's telemetry, suitable for scraping.
+    pub fn metrics_text(&self) -> String {
+        let stats = self.sdk.statistics();
+        let mut out = String::new();
+
+        macro_rules! counter {
+            ($name:expr, $help:expr, $value:expr) => {
+                out.push_str(&format!("# HELP {} {}\n# TYPE {} counter\n{} {}\n", $name, $help, $name, $name, $value));
+            };
+        }
+
+        counter!("bp_sdk_bundles_sent_total", "Total bundles sent", stats.bundles_sent);
+        counter!("bp_sdk_bundles_received_total", "Total bundles received", stats.bundles_received);
+        counter!("bp_sdk_bundles_forwarded_total", "Total bundles forwarded", stats.bundles_forwarded);
+        counter!("bp_sdk_bundles_delivered_total", "Total bundles delivered", stats.bundles_delivered);
+        counter!("bp_sdk_bundles_deleted_total", "Total bundles deleted", stats.bundles_deleted);
+        counter!("bp_sdk_bytes_sent_total", "Total bytes sent", stats.bytes_sent);
+        counter!("bp_sdk_bytes_received_total", "Total bytes received", stats.bytes_received);
+
+        out.push_str(&self.routing.metrics_text());
+        out
+    }
+
+    /// Look up the best route for `bundle` via the active routing engine, e.g. for a caller
+    /// that wants to inspect the next hop before sending. `clas.send_to_eid` consults the same
+    /// routing engine internally (it was attached via `set_routing` in `NodeConfig::build`), so
+    /// a CGR-computed route drives transmission whether or not a caller checks here first.
+    /// Under contact graph routing this consults the scheduled contact plan for a
+    /// proactively-timed next hop rather than a static table.
+    pub fn route_for(&self, bundle: &Bundle) -> Option<Route> {
+        self.routing.find_route(&bundle.dest_eid, bundle.payload_size(), Utc::now())
+    }
+}
+
+impl NodeConfig {
+    /// Load and validate a node profile from a YAML file.
+    pub fn from_yaml(path: impl AsRef<Path>) -> BpResult<Self> {
+        let contents = std::fs::read_to_string(path).map_err(|_| BpError::InvalidArgs)?;
+        let config: Self = serde_yaml::from_str(&contents).map_err(|_| BpError::InvalidArgs)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Write this profile out as YAML.
+    pub fn save(&self, path: impl AsRef<Path>) -> BpResult<()> {
+        let yaml = serde_yaml::to_string(self).map_err(|_| BpError::InvalidArgs)?;
+        std::fs::write(path, yaml).map_err(|_| BpError::InvalidArgs)
+    }
+
+    fn validate(&self) -> BpResult<()> {
+        Eid::new(self.node_eid.as_str())?;
+
+        for transport in &self.transports {
+            if !KNOWN_PROTOCOLS.contains(&transport.protocol.as_str()) {
+                return Err(BpError::InvalidArgs);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Interactively prompt an operator for a node's configuration and save it to `path`.
+    pub fn wizard(path: impl AsRef<Path>) -> BpResult<Self> {
+        let stdin = io::stdin();
+        let mut lines = stdin.lock().lines();
+
+        let node_eid = prompt(&mut lines, "Local EID (e.g. ipn:1.1): ")?;
+        Eid::new(node_eid.as_str())?;
+
+        let mut transports = Vec::new();
+        loop {
+            let protocol = prompt(&mut lines, "Transport protocol (tcp/udp, blank to stop): ")?;
+            if protocol.is_empty() {
+                break;
+            }
+            if !KNOWN_PROTOCOLS.contains(&protocol.as_str()) {
+                println!("Unknown protocol '{}', expected one of {:?}", protocol, KNOWN_PROTOCOLS);
+                continue;
+            }
+
+            let listen_address = prompt(&mut lines, "  Listen address: ")?;
+            let advertised = prompt(&mut lines, "  Advertised address (blank if same as listen): ")?;
+
+            transports.push(TransportProfile {
+                protocol,
+                listen_address,
+                advertised_address: if advertised.is_empty() { None } else { Some(advertised) },
+            });
+        }
+
+        let routing_engine = prompt(&mut lines, "Routing engine (epidemic/spray_and_wait/prophet/contact_graph): ")?;
+
+        let trust_mode = prompt(&mut lines, "BPSec trust mode (shared-secret/explicit/blank to skip): ")?;
+        let security = if trust_mode.is_empty() {
+            SecurityProfile::default()
+        } else {
+            let identity_secret = prompt(&mut lines, "  Identity secret or key path: ")?;
+            let mut trusted_key_paths = Vec::new();
+            loop {
+                let key_path = prompt(&mut lines, "  Trusted peer key path (blank to stop): ")?;
+                if key_path.is_empty() {
+                    break;
+                }
+                trusted_key_paths.push(key_path);
+            }
+
+            SecurityProfile {
+                trust_mode: Some(trust_mode),
+                identity_secret: if identity_secret.is_empty() { None } else { Some(identity_secret) },
+                trusted_key_paths,
+            }
+        };
+
+        let config = Self { node_eid, transports, routing_engine, security };
+        config.validate()?;
+        config.save(path)?;
+        Ok(config)
+    }
+
+    /// Construct a ready-to-run node: CLAs registered, routing engine selected, and BPSec
+    /// trust installed from this profile.
+    pub async fn build(&self) -> BpResult<NodeHandle> {
+        self.validate()?;
+        let node_eid = Eid::new(self.node_eid.as_str())?;
+
+        let sdk = BpSdk::new(node_eid, None)?;
+
+        let clas = ClaManager::new();
+        for transport in &self.transports {
+            let mut config = match transport.protocol.as_str() {
+                "tcp" => TransportConfig::tcp(transport.listen_address.as_str()),
+                "udp" => TransportConfig::udp(transport.listen_address.as_str()),
+                _ => return Err(BpError::InvalidArgs),
+            };
+            config.remote_address = transport.advertised_address.clone();
+
+            let cla: Arc<dyn crate::cla::Cla> = match transport.protocol.as_str() {
+                "tcp" => {
+                    let cla = crate::cla::TcpCla::new(config)?;
+                    let sdk_for_hook = sdk.clone();
+                    cla.on_reconnect_attempt(move || sdk_for_hook.record_reconnect_attempt());
+                    let sdk_for_transfer = sdk.clone();
+                    cla.on_bundle_transfer(move |sent, bytes| {
+                        if sent {
+                            sdk_for_transfer.record_bundle_sent(bytes);
+                        } else {
+                            sdk_for_transfer.record_bundle_received(bytes);
+                        }
+                    });
+                    Arc::new(cla)
+                }
+                "udp" => {
+                    let cla = crate::cla::UdpCla::new(config)?;
+                    let sdk_for_hook = sdk.clone();
+                    cla.on_reconnect_attempt(move || sdk_for_hook.record_reconnect_attempt());
+                    Arc::new(cla)
+                }
+                _ => return Err(BpError::InvalidArgs),
+            };
+            clas.register(cla)?;
+        }
+
+        let routing = Arc::new(RoutingManager::new());
+        if !self.routing_engine.is_empty() {
+            routing.set_active_engine(&self.routing_engine)?;
+        }
+        clas.set_routing(routing.clone());
+
+        let security = BpsecManager::new();
+        if let Some(mode) = &self.security.trust_mode {
+            let trust_mode = match mode.as_str() {
+                "shared-secret" => TrustMode::SharedSecret,
+                "explicit" => TrustMode::Explicit,
+                _ => return Err(BpError::InvalidArgs),
+            };
+
+            let secret = self.security.identity_secret.as_deref().unwrap_or("");
+            security.set_identity(trust_mode, secret.as_bytes())?;
+
+            for key_path in &self.security.trusted_key_paths {
+                let key = std::fs::read(key_path).map_err(|_| BpError::InvalidArgs)?;
+                security.add_trusted_key(bytes::Bytes::from(key));
+            }
+        }
+
+        Ok(NodeHandle { sdk, clas, routing, security })
+    }
+}
+
+fn prompt(lines: &mut io::Lines<io::StdinLock<'_>>, message: &str) -> BpResult<String> {
+    print!("{}", message);
+    io::stdout().flush().ok();
+
+    let line = lines
+        .next()
+        .ok_or(BpError::InvalidArgs)?
+        .map_err(|_| BpError::InvalidArgs)?;
+    Ok(line.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_yaml_round_trip_and_validation() {
+        let config = NodeConfig {
+            node_eid: "ipn:1.1".to_string(),
+            transports: vec![TransportProfile {
+                protocol: "tcp".to_string(),
+                listen_address: "0.0.0.0:4556".to_string(),
+                advertised_address: Some("203.0.113.5:4556".to_string()),
+            }],
+            routing_engine: "epidemic".to_string(),
+            security: SecurityProfile::default(),
+        };
+
+        let path = std::env::temp_dir().join(format!("bp-sdk-test-node-{:?}.yaml", std::thread::current().id()));
+        config.save(&path).unwrap();
+
+        let loaded = NodeConfig::from_yaml(&path).unwrap();
+        assert_eq!(loaded.node_eid, config.node_eid);
+        assert_eq!(loaded.transports.len(), 1);
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_invalid_eid_rejected() {
+        let config = NodeConfig {
+            node_eid: "not-an-eid".to_string(),
+            transports: vec![],
+            routing_engine: "epidemic".to_string(),
+            security: SecurityProfile::default(),
+        };
+
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_unknown_protocol_rejected() {
+        let config = NodeConfig {
+            node_eid: "ipn:1.1".to_string(),
+            transports: vec![TransportProfile {
+                protocol: "carrier-pigeon".to_string(),
+                listen_address: "n/a".to_string(),
+                advertised_address: None,
+            }],
+            routing_engine: "epidemic".to_string(),
+            security: SecurityProfile::default(),
+        };
+
+        assert!(config.validate().is_err());
+    }
+}
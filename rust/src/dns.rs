@@ -0,0 +1,114 @@
+//! Pluggable hostname resolution for CLA endpoints.
+//!
+//! `TransportConfig::local_address` and the `dest_addr` a CLA dials both accept either a
+//! literal `ip:port` or a `host:port` that needs resolving. [`DnsResolver`] abstracts that
+//! lookup so [`crate::cla::TcpCla`]/[`crate::cla::UdpCla`] can re-resolve on every
+//! reconnect attempt (picking up a rotated A/AAAA record) and so tests/disconnected
+//! deployments can inject a fixed or cached table instead of hitting a live resolver.
+
+use crate::error::{BpError, BpResult};
+use async_trait::async_trait;
+use parking_lot::RwLock;
+use std::{collections::HashMap, fmt::Debug, net::SocketAddr};
+
+/// Resolves a `host:port` (or already-literal `ip:port`) string to a [`SocketAddr`].
+#[async_trait]
+pub trait DnsResolver: Send + Sync + Debug {
+    async fn resolve(&self, address: &str) -> BpResult<SocketAddr>;
+}
+
+/// Resolves via the OS resolver, taking the first address it returns. Literal `ip:port`
+/// addresses are passed straight through without a lookup.
+#[derive(Debug, Default)]
+pub struct SystemResolver;
+
+#[async_trait]
+impl DnsResolver for SystemResolver {
+    async fn resolve(&self, address: &str) -> BpResult<SocketAddr> {
+        if let Ok(addr) = address.parse::<SocketAddr>() {
+            return Ok(addr);
+        }
+
+        tokio::net::lookup_host(address)
+            .await
+            .map_err(|e| BpError::Protocol(format!("DNS resolution failed for {}: {}", address, e)))?
+            .next()
+            .ok_or_else(|| BpError::Protocol(format!("no addresses found for {}", address)))
+    }
+}
+
+/// A fixed `host:port -> SocketAddr` table, for tests and split-horizon/cached-lookup
+/// deployments that don't want to hit a live resolver. Literal `ip:port` addresses still
+/// pass straight through without needing an entry.
+#[derive(Debug, Default)]
+pub struct StaticResolver {
+    entries: RwLock<HashMap<String, SocketAddr>>,
+}
+
+impl StaticResolver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an entry, chainable for construction (`StaticResolver::new().with_entry(...)`).
+    pub fn with_entry(self, host: impl Into<String>, addr: SocketAddr) -> Self {
+        self.insert(host, addr);
+        self
+    }
+
+    /// Add or replace an entry, e.g. after observing a relay's address change.
+    pub fn insert(&self, host: impl Into<String>, addr: SocketAddr) {
+        self.entries.write().insert(host.into(), addr);
+    }
+}
+
+#[async_trait]
+impl DnsResolver for StaticResolver {
+    async fn resolve(&self, address: &str) -> BpResult<SocketAddr> {
+        if let Ok(addr) = address.parse::<SocketAddr>() {
+            return Ok(addr);
+        }
+
+        self.entries
+            .read()
+            .get(address)
+            .copied()
+            .ok_or_else(|| BpError::Protocol(format!("no static resolver entry for {}", address)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_static_resolver_passes_literal_addr_through() {
+        let resolver = StaticResolver::new();
+        let addr: SocketAddr = "127.0.0.1:4556".parse().unwrap();
+        assert_eq!(resolver.resolve("127.0.0.1:4556").await.unwrap(), addr);
+    }
+
+    #[tokio::test]
+    async fn test_static_resolver_looks_up_hostname() {
+        let addr: SocketAddr = "10.0.0.5:4556".parse().unwrap();
+        let resolver = StaticResolver::new().with_entry("relay.example.org:4556", addr);
+        assert_eq!(resolver.resolve("relay.example.org:4556").await.unwrap(), addr);
+    }
+
+    #[tokio::test]
+    async fn test_static_resolver_reflects_updated_entry() {
+        let first: SocketAddr = "10.0.0.5:4556".parse().unwrap();
+        let second: SocketAddr = "10.0.0.6:4556".parse().unwrap();
+        let resolver = StaticResolver::new().with_entry("relay.example.org:4556", first);
+        assert_eq!(resolver.resolve("relay.example.org:4556").await.unwrap(), first);
+
+        resolver.insert("relay.example.org:4556", second);
+        assert_eq!(resolver.resolve("relay.example.org:4556").await.unwrap(), second);
+    }
+
+    #[tokio::test]
+    async fn test_static_resolver_errors_on_unknown_hostname() {
+        let resolver = StaticResolver::new();
+        assert!(resolver.resolve("unknown.example.org:4556").await.is_err());
+    }
+}
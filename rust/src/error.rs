@@ -35,7 +35,10 @@ pub enum BpError {
     
     #[error("Security error: {0}")]
     Security(String),
-    
+
+    #[error("TLS handshake failed: {0}")]
+    TlsHandshake(String),
+
     #[error("ION-DTN error: {code}")]
     Ion { code: i32 },
     
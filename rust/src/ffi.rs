@@ -86,7 +86,18 @@ extern "C" {
         buffer: *mut c_char,
     ) -> c_int;
     pub fn zco_source_data_length(sdr: *mut c_void, zco: c_uint) -> size_t;
-    
+
+    /// Appends one more source-data extent to an already-created ZCO, so a payload can be
+    /// assembled incrementally from several SDR objects instead of requiring the whole thing
+    /// up front in one `ion_create_zco` call.
+    pub fn zco_append_extent(
+        sdr: *mut c_void,
+        zco: c_uint,
+        source_data: c_uint,
+        offset: size_t,
+        length: size_t,
+    ) -> c_int;
+
     // ION Admin functions
     pub fn add_plan(dest_eid: *mut c_char, nominal_rate: c_uint) -> c_int;
     pub fn remove_plan(dest_eid: *mut c_char) -> c_int;
@@ -0,0 +1,300 @@
+//! Bundle fragmentation with Merkle-tree integrity proofs.
+//!
+//! [`Bundle::fragment`] splits a bundle's payload into `max_chunk`-sized pieces small enough
+//! for a CLA's `max_payload_size`, computing a SHA3-256 Merkle tree over the ordered chunks so
+//! every fragment carries a sibling-hash inclusion path back to a single root. A
+//! [`FragmentReassembler`] verifies each fragment against that root as it arrives — in any
+//! order, with any subset missing so far — and only yields the reconstructed `Bundle` once
+//! every byte offset is covered contiguously.
+
+use crate::{
+    error::{BpError, BpResult},
+    types::Bundle,
+};
+use bytes::{Bytes, BytesMut};
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+use std::collections::BTreeMap;
+
+/// A SHA3-256 digest.
+pub type Sha3Hash = [u8; 32];
+
+fn hash_leaf(chunk: &[u8]) -> Sha3Hash {
+    let mut hasher = Sha3_256::new();
+    hasher.update(chunk);
+    hasher.finalize().into()
+}
+
+fn hash_pair(left: &Sha3Hash, right: &Sha3Hash) -> Sha3Hash {
+    let mut hasher = Sha3_256::new();
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+/// One step of a Merkle inclusion proof: a sibling hash and which side of the pair it occupies.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MerkleStep {
+    pub sibling: Sha3Hash,
+    pub sibling_is_left: bool,
+}
+
+/// Builds a Merkle root over `leaves` (duplicating the last leaf of any odd-sized level) and,
+/// for each leaf, the inclusion path of sibling hashes needed to recompute that root.
+fn merkle_root_and_proofs(leaves: Vec<Sha3Hash>) -> (Sha3Hash, Vec<Vec<MerkleStep>>) {
+    let leaf_count = leaves.len();
+    let mut level = leaves;
+    let mut positions: Vec<usize> = (0..leaf_count).collect();
+    let mut proofs: Vec<Vec<MerkleStep>> = vec![Vec::new(); leaf_count];
+
+    while level.len() > 1 {
+        if level.len() % 2 == 1 {
+            level.push(*level.last().unwrap());
+        }
+
+        for leaf_idx in 0..leaf_count {
+            let pos = positions[leaf_idx];
+            let sibling_idx = pos ^ 1;
+            proofs[leaf_idx].push(MerkleStep {
+                sibling: level[sibling_idx],
+                sibling_is_left: sibling_idx < pos,
+            });
+        }
+
+        let next_level = level.chunks_exact(2).map(|pair| hash_pair(&pair[0], &pair[1])).collect();
+        for pos in positions.iter_mut() {
+            *pos /= 2;
+        }
+        level = next_level;
+    }
+
+    (level[0], proofs)
+}
+
+/// Recomputes the Merkle root implied by `leaf` and `proof` and checks it against `root`.
+fn verify_proof(leaf: &Sha3Hash, proof: &[MerkleStep], root: &Sha3Hash) -> bool {
+    let mut acc = *leaf;
+    for step in proof {
+        acc = if step.sibling_is_left {
+            hash_pair(&step.sibling, &acc)
+        } else {
+            hash_pair(&acc, &step.sibling)
+        };
+    }
+    &acc == root
+}
+
+/// One piece of a fragmented [`Bundle`], carrying enough to be verified and placed independently
+/// of the others: its position, a proof of inclusion in the whole payload's Merkle tree, and
+/// (only on fragment 0) the original bundle's header.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleFragment {
+    pub chunk_index: u32,
+    pub chunk_count: u32,
+    pub offset: u64,
+    pub total_len: u64,
+    pub data: Bytes,
+    pub merkle_root: Sha3Hash,
+    pub proof: Vec<MerkleStep>,
+    /// The original bundle's fields with its `payload` cleared, present only on `chunk_index`
+    /// 0 so it isn't repeated in every fragment.
+    pub header: Option<Bundle>,
+}
+
+impl Bundle {
+    /// Split this bundle's payload into `max_chunk`-sized (or smaller) fragments, each proved
+    /// against a Merkle root computed over every chunk. `max_chunk` must be nonzero.
+    pub fn fragment(&self, max_chunk: usize) -> BpResult<Vec<BundleFragment>> {
+        if max_chunk == 0 {
+            return Err(BpError::InvalidArgs);
+        }
+
+        let total_len = self.payload.len() as u64;
+        let chunks: Vec<Bytes> = if self.payload.is_empty() {
+            vec![Bytes::new()]
+        } else {
+            self.payload.chunks(max_chunk).map(Bytes::copy_from_slice).collect()
+        };
+
+        let leaves: Vec<Sha3Hash> = chunks.iter().map(|chunk| hash_leaf(chunk)).collect();
+        let (root, proofs) = merkle_root_and_proofs(leaves);
+        let chunk_count = chunks.len() as u32;
+
+        let mut offset = 0u64;
+        let mut fragments = Vec::with_capacity(chunks.len());
+        for (index, chunk) in chunks.into_iter().enumerate() {
+            let header = if index == 0 {
+                let mut header_bundle = self.clone();
+                header_bundle.payload = Bytes::new();
+                Some(header_bundle)
+            } else {
+                None
+            };
+
+            let chunk_len = chunk.len() as u64;
+            fragments.push(BundleFragment {
+                chunk_index: index as u32,
+                chunk_count,
+                offset,
+                total_len,
+                data: chunk,
+                merkle_root: root,
+                proof: proofs[index].clone(),
+                header,
+            });
+            offset += chunk_len;
+        }
+
+        Ok(fragments)
+    }
+}
+
+/// Verifies and reassembles [`BundleFragment`]s produced by [`Bundle::fragment`], accepting
+/// them in any order and tolerating gaps until every byte offset is covered.
+#[derive(Debug, Default)]
+pub struct FragmentReassembler {
+    total_len: Option<u64>,
+    merkle_root: Option<Sha3Hash>,
+    header: Option<Bundle>,
+    chunks: BTreeMap<u64, Bytes>,
+}
+
+impl FragmentReassembler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Verify `fragment`'s inclusion proof and absorb it, erroring with
+    /// [`BpError::InvalidArgs`] if the proof doesn't recompute to the fragment's claimed root
+    /// or that root/length disagrees with a previously ingested fragment. Returns the
+    /// reassembled bundle once every offset from `0..total_len` is contiguously covered and the
+    /// header fragment (chunk 0) has arrived; otherwise `None`.
+    pub fn ingest(&mut self, fragment: BundleFragment) -> BpResult<Option<Bundle>> {
+        let leaf = hash_leaf(&fragment.data);
+        if !verify_proof(&leaf, &fragment.proof, &fragment.merkle_root) {
+            return Err(BpError::InvalidArgs);
+        }
+
+        match self.merkle_root {
+            Some(root) if root != fragment.merkle_root => return Err(BpError::InvalidArgs),
+            _ => self.merkle_root = Some(fragment.merkle_root),
+        }
+        match self.total_len {
+            Some(len) if len != fragment.total_len => return Err(BpError::InvalidArgs),
+            _ => self.total_len = Some(fragment.total_len),
+        }
+
+        if let Some(header) = fragment.header {
+            self.header = Some(header);
+        }
+        self.chunks.insert(fragment.offset, fragment.data);
+
+        Ok(self.try_reassemble())
+    }
+
+    fn try_reassemble(&self) -> Option<Bundle> {
+        let total_len = self.total_len?;
+        let header = self.header.as_ref()?;
+
+        let mut expected_offset = 0u64;
+        let mut payload = BytesMut::with_capacity(total_len as usize);
+        for (&offset, chunk) in self.chunks.iter() {
+            if offset != expected_offset {
+                return None;
+            }
+            payload.extend_from_slice(chunk);
+            expected_offset += chunk.len() as u64;
+        }
+
+        if expected_offset != total_len {
+            return None;
+        }
+
+        let mut bundle = header.clone();
+        bundle.payload = payload.freeze();
+        Some(bundle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Eid;
+
+    fn sample_bundle(payload_len: usize) -> Bundle {
+        let payload: Vec<u8> = (0..payload_len).map(|i| (i % 256) as u8).collect();
+        Bundle::new(Eid::new("ipn:1.1").unwrap(), Eid::new("ipn:2.1").unwrap(), payload)
+    }
+
+    #[test]
+    fn test_fragment_and_reassemble_round_trip() {
+        let bundle = sample_bundle(1000);
+        let fragments = bundle.fragment(64).unwrap();
+        assert!(fragments.len() > 1);
+
+        let mut reassembler = FragmentReassembler::new();
+        let mut result = None;
+        for fragment in fragments {
+            result = reassembler.ingest(fragment).unwrap();
+        }
+
+        let reassembled = result.unwrap();
+        assert_eq!(reassembled.payload, bundle.payload);
+        assert_eq!(reassembled.source_eid, bundle.source_eid);
+    }
+
+    #[test]
+    fn test_reassembly_tolerates_out_of_order_and_gaps() {
+        let bundle = sample_bundle(500);
+        let mut fragments = bundle.fragment(32).unwrap();
+        fragments.reverse();
+
+        let mut reassembler = FragmentReassembler::new();
+        let mut result = None;
+        for fragment in fragments {
+            result = reassembler.ingest(fragment).unwrap();
+        }
+
+        assert_eq!(result.unwrap().payload, bundle.payload);
+    }
+
+    #[test]
+    fn test_odd_chunk_count_round_trip() {
+        let bundle = sample_bundle(777);
+        let fragments = bundle.fragment(120).unwrap();
+        assert_eq!(fragments.len(), 7);
+
+        let mut reassembler = FragmentReassembler::new();
+        let mut result = None;
+        for fragment in fragments {
+            result = reassembler.ingest(fragment).unwrap();
+        }
+
+        assert_eq!(result.unwrap().payload, bundle.payload);
+    }
+
+    #[test]
+    fn test_tampered_fragment_rejected() {
+        let bundle = sample_bundle(1000);
+        let mut fragments = bundle.fragment(64).unwrap();
+        fragments[2].data = Bytes::from_static(b"corrupted");
+
+        let mut reassembler = FragmentReassembler::new();
+        assert!(reassembler.ingest(fragments.remove(2)).is_err());
+    }
+
+    #[test]
+    fn test_incomplete_fragments_yield_nothing() {
+        let bundle = sample_bundle(500);
+        let mut fragments = bundle.fragment(32).unwrap();
+        fragments.pop();
+
+        let mut reassembler = FragmentReassembler::new();
+        let mut result = None;
+        for fragment in fragments {
+            result = reassembler.ingest(fragment).unwrap();
+        }
+
+        assert!(result.is_none());
+    }
+}
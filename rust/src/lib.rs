@@ -7,24 +7,57 @@ pub mod types;
 pub mod ffi;
 pub mod core;
 pub mod cla;
+pub mod codec;
+pub mod tcpcl;
+pub mod tls;
+pub mod quic;
+pub mod store;
+pub mod backoff;
+pub mod dns;
+pub mod peers;
+pub mod reporter;
 pub mod bpsec;
+pub mod fragment;
 pub mod routing;
 pub mod metrics;
+#[cfg(feature = "metrics-http")]
+pub mod metrics_http;
 pub mod testing;
+pub mod config;
 
 pub use error::{BpError, BpResult};
 pub use types::{Bundle, Custody, Eid, Priority, Statistics, Route, Contact, Range, TransportConfig, BpTimestamp};
 pub use core::{BpSdk, Endpoint};
-pub use cla::{Cla, ClaManager, TcpCla, UdpCla};
-pub use bpsec::{BpsecManager, SecurityBlock, SecurityPolicy};
-pub use routing::{RoutingEngine, EpidemicRouting, SprayAndWaitRouting};
+pub use cla::{Cla, ClaManager, ConnectionState, TcpCla, UdpCla, UnixCla, WsCla};
+pub use codec::{BundleCodec, JsonCodec, CborCodec, BincodeCodec, MsgPackCodec, codec_for};
+pub use tcpcl::{ContactHeader, SessionEvent, SessionState, TcpClMessage, TcpClSession};
+pub use tls::TlsProfile;
+pub use quic::QuicCla;
+pub use store::{BundleStore, InMemoryBundleStore, StoredBundle, BundleId};
+pub use backoff::BackoffPolicy;
+pub use dns::{DnsResolver, StaticResolver, SystemResolver};
+pub use peers::{PeerManager, NeighborRecord, PeerEvent, Liveness, hello_bundle};
+pub use reporter::{MetricsReporter, MetricsEvent};
+pub use bpsec::{
+    BpsecManager, SecurityBlock, SecurityPolicy, RekeyPolicy, RekeyTrigger,
+    CryptoProvider, RingCryptoProvider, DummyCryptoProvider,
+};
+pub use fragment::{BundleFragment, FragmentReassembler, MerkleStep, Sha3Hash};
+pub use routing::{
+    RoutingEngine, EpidemicRouting, SprayAndWaitRouting, SprayMode, ContactGraphRouting,
+    ContactPlan, RoutingStore, FileRoutingStore, FlushPolicy,
+};
 pub use metrics::{MetricsCollector, PerformanceMetrics};
+#[cfg(feature = "metrics-http")]
+pub use metrics_http::serve_metrics;
+pub use config::{NodeConfig, NodeHandle, TransportProfile, SecurityProfile};
 
 pub mod prelude {
     pub use crate::{
         BpSdk, Endpoint, Bundle, Eid, Priority, Custody, 
         BpError, BpResult, Cla, ClaManager, BpsecManager,
-        RoutingEngine, MetricsCollector, Route, Contact
+        RoutingEngine, MetricsCollector, Route, Contact,
+        RekeyPolicy, RekeyTrigger, NodeConfig, NodeHandle
     };
 }
 
@@ -48,6 +81,23 @@ mod tests {
         let parsed: Eid = "ipn:789.012".parse().unwrap();
         assert_eq!(parsed.node_number(), Some(789));
         assert_eq!(parsed.service_number(), Some(12));
+
+        assert!(Eid::new("dtn://node1/service1").is_ok());
+        assert!(Eid::new("dtn:none").is_ok());
+        assert!(Eid::new("dtn://").is_err());
+
+        let dtn_eid = Eid::new("dtn://node1/service1").unwrap();
+        assert_eq!(dtn_eid.scheme(), "dtn");
+        assert_eq!(dtn_eid.demux(), Some("service1"));
+        assert!(!dtn_eid.is_null());
+
+        let null_eid = Eid::dtn_none();
+        assert!(null_eid.is_null());
+        assert_eq!(null_eid.scheme(), "dtn");
+
+        let ipn_eid = Eid::ipn(1, 1);
+        assert_eq!(ipn_eid.scheme(), "ipn");
+        assert_eq!(ipn_eid.demux(), None);
     }
 
     #[test]
@@ -74,6 +124,32 @@ mod tests {
         assert!(short_bundle.is_expired());
     }
 
+    #[test]
+    fn test_bundle_cbor_round_trip() {
+        let source = Eid::new("ipn:1.1").unwrap();
+        let dest = Eid::new("ipn:2.1").unwrap();
+
+        let bundle = Bundle::new(source.clone(), dest.clone(), "Hello, BPv7!")
+            .with_priority(Priority::Expedited)
+            .with_custody(Custody::Required)
+            .with_ttl(Duration::from_secs(3600));
+
+        let encoded = bundle.to_cbor().unwrap();
+        let decoded = Bundle::from_cbor(&encoded).unwrap();
+
+        assert_eq!(decoded.source_eid, source);
+        assert_eq!(decoded.dest_eid, dest);
+        assert_eq!(decoded.priority, Priority::Expedited);
+        assert_eq!(decoded.custody, Custody::Required);
+        assert_eq!(decoded.payload, bundle.payload);
+        assert_eq!(decoded.creation_time, bundle.creation_time);
+
+        let mut corrupted = encoded.to_vec();
+        let last = corrupted.len() - 1;
+        corrupted[last] ^= 0xFF;
+        assert!(Bundle::from_cbor(&corrupted).is_err());
+    }
+
     #[test]
     fn test_route_and_contact_validity() {
         let dest = Eid::new("ipn:2.1").unwrap();
@@ -95,13 +171,37 @@ mod tests {
         assert_eq!(contact.duration(), chrono::Duration::hours(2));
     }
 
+    struct FixedClock(u64);
+
+    impl types::Clock for FixedClock {
+        fn now_millis(&self) -> u64 {
+            self.0
+        }
+    }
+
+    #[test]
+    fn test_clock_trait_drives_bundle_expiry_without_system_time() {
+        let source = Eid::new("ipn:1.1").unwrap();
+        let dest = Eid::new("ipn:2.1").unwrap();
+
+        let bundle = Bundle::with_clock(source, dest, "test", &FixedClock(1_000))
+            .with_ttl(Duration::from_secs(1));
+
+        assert!(!bundle.is_expired_at(1_500));
+        assert!(bundle.is_expired_at(3_000));
+    }
+
     #[tokio::test]
     async fn test_cla_manager_operations() {
         let manager = ClaManager::new();
         assert!(manager.list_protocols().is_empty());
         
-        let _udp_cla = manager.create_udp_cla("127.0.0.1:0").await.unwrap();
-        let _tcp_cla = manager.create_tcp_cla("127.0.0.1:0").await.unwrap();
+        let retry_policy = BackoffPolicy::new(Duration::from_millis(100), 2.0, Duration::from_secs(5), 3);
+        let resolver: std::sync::Arc<dyn DnsResolver> = std::sync::Arc::new(SystemResolver);
+        let _udp_cla = manager.create_udp_cla("127.0.0.1:0", retry_policy.clone(), resolver.clone()).await.unwrap();
+        let _tcp_cla = manager.create_tcp_cla("127.0.0.1:0", retry_policy, resolver).await.unwrap();
+        assert_eq!(_tcp_cla.connection_state(), crate::cla::ConnectionState::Connected);
+        assert_eq!(_tcp_cla.retry_count(), 0);
         
         let protocols = manager.list_protocols();
         assert_eq!(protocols.len(), 2);
@@ -196,6 +296,24 @@ mod tests {
         assert!(!routes.is_empty());
     }
 
+    #[test]
+    fn test_contact_graph_routing() {
+        let dest = Eid::new("ipn:2.1").unwrap();
+        let contact = Contact::new(
+            dest.clone(),
+            chrono::Utc::now() - chrono::Duration::seconds(1),
+            chrono::Utc::now() + chrono::Duration::hours(1),
+            1_000_000,
+        );
+
+        let cgr = ContactGraphRouting::new(Vec::new(), Vec::new());
+        assert_eq!(cgr.name(), "contact_graph");
+
+        let routes = cgr.compute_routes(&dest, &[contact]);
+        assert!(!routes.is_empty());
+        assert_eq!(routes[0].next_hop, dest);
+    }
+
     #[test]
     fn test_metrics_collection() {
         let collector = MetricsCollector::new();
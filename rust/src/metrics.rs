@@ -5,6 +5,14 @@ use std::collections::{HashMap, VecDeque};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{Duration, Instant};
 
+use bandwidth::StatsAccounting;
+use histogram::LatencyHistogram;
+
+/// `le` boundaries (milliseconds) used for the `bp_latency_ms` Prometheus histogram, roughly
+/// log-spaced to match typical DTN round-trip/store-and-forward delays.
+const PROMETHEUS_LATENCY_BUCKETS_MS: [f64; 10] =
+    [5.0, 10.0, 25.0, 50.0, 100.0, 250.0, 500.0, 1000.0, 2500.0, 5000.0];
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PerformanceMetrics {
     pub bundles_sent: u64,
@@ -18,7 +26,24 @@ pub struct PerformanceMetrics {
     pub latency_avg_ms: f64,
     pub latency_min_ms: f64,
     pub latency_max_ms: f64,
+    pub latency_p50_ms: f64,
+    pub latency_p90_ms: f64,
+    pub latency_p99_ms: f64,
+    pub latency_p999_ms: f64,
+    /// Number of latency samples the above `latency_avg_ms`/`min`/`max` figures were computed
+    /// from (the most recent `max_latency_samples`), so aggregation across multiple collectors
+    /// can weight by traffic volume instead of averaging averages. The `latency_p*` figures
+    /// come from a histogram of every sample recorded since the last reset.
+    pub latency_sample_count: u64,
+    pub incoming_avg_bps: f64,
+    pub incoming_max_bps: f64,
+    pub outgoing_avg_bps: f64,
+    pub outgoing_max_bps: f64,
     pub throughput_bps: f64,
+    /// Bundles/sec rate for a specific window, set by `MetricsCollector::aggregate_window`.
+    /// Left at `0.0` on snapshots from `get_metrics`/`snapshot`, which report lifetime
+    /// cumulative counts rather than a rate.
+    pub bundles_per_sec: f64,
     pub delivery_ratio: f64,
     pub buffer_utilization: f64,
     pub active_connections: u32,
@@ -39,7 +64,17 @@ impl PerformanceMetrics {
             latency_avg_ms: 0.0,
             latency_min_ms: f64::MAX,
             latency_max_ms: 0.0,
+            latency_p50_ms: 0.0,
+            latency_p90_ms: 0.0,
+            latency_p99_ms: 0.0,
+            latency_p999_ms: 0.0,
+            latency_sample_count: 0,
+            incoming_avg_bps: 0.0,
+            incoming_max_bps: 0.0,
+            outgoing_avg_bps: 0.0,
+            outgoing_max_bps: 0.0,
             throughput_bps: 0.0,
+            bundles_per_sec: 0.0,
             delivery_ratio: 0.0,
             buffer_utilization: 0.0,
             active_connections: 0,
@@ -152,9 +187,11 @@ pub struct MetricsCollector {
     bytes_received: AtomicU64,
     
     latency_measurements: RwLock<VecDeque<LatencyMeasurement>>,
+    latency_histogram: LatencyHistogram,
+    stats: StatsAccounting,
     connection_metrics: RwLock<HashMap<String, ConnectionMetrics>>,
     historical_metrics: RwLock<VecDeque<PerformanceMetrics>>,
-    
+
     start_time: Instant,
     max_history_size: usize,
     max_latency_samples: usize,
@@ -173,6 +210,8 @@ impl MetricsCollector {
             bytes_received: AtomicU64::new(0),
             
             latency_measurements: RwLock::new(VecDeque::new()),
+            latency_histogram: LatencyHistogram::new(),
+            stats: StatsAccounting::new(),
             connection_metrics: RwLock::new(HashMap::new()),
             historical_metrics: RwLock::new(VecDeque::new()),
             
@@ -195,16 +234,19 @@ impl MetricsCollector {
     pub fn record_bundle_sent(&self, bytes: u64) {
         self.bundles_sent.fetch_add(1, Ordering::Relaxed);
         self.bytes_sent.fetch_add(bytes, Ordering::Relaxed);
+        self.stats.record_outgoing(bytes);
     }
 
     pub fn record_bundle_received(&self, bytes: u64) {
         self.bundles_received.fetch_add(1, Ordering::Relaxed);
         self.bytes_received.fetch_add(bytes, Ordering::Relaxed);
+        self.stats.record_incoming(bytes);
     }
 
     pub fn record_bundle_forwarded(&self, bytes: u64) {
         self.bundles_forwarded.fetch_add(1, Ordering::Relaxed);
         self.bytes_sent.fetch_add(bytes, Ordering::Relaxed);
+        self.stats.record_outgoing(bytes);
     }
 
     pub fn record_bundle_delivered(&self) {
@@ -220,14 +262,31 @@ impl MetricsCollector {
     }
 
     pub fn record_latency(&self, measurement: LatencyMeasurement) {
+        self.latency_histogram.record(measurement.latency_ms);
+
         let mut latencies = self.latency_measurements.write();
         latencies.push_back(measurement);
-        
+
         if latencies.len() > self.max_latency_samples {
             latencies.pop_front();
         }
     }
 
+    /// The `p`th percentile (0-100) of recorded latencies in milliseconds, backed by an
+    /// HDR-style histogram so the query is O(bucket count) regardless of how many samples
+    /// have ever been recorded.
+    pub fn latency_percentile(&self, p: f64) -> f64 {
+        self.latency_histogram.percentile(p)
+    }
+
+    /// A snapshot of this collector's raw histogram bucket counts, for merging several
+    /// collectors' histograms into one before computing a global percentile (see
+    /// [`MetricsAggregator::aggregate_metrics`]) rather than averaging per-collector
+    /// percentiles, which does not commute.
+    pub fn latency_histogram_counts(&self) -> Vec<u64> {
+        self.latency_histogram.counts_snapshot()
+    }
+
     pub fn record_connection_activity(&self, connection_id: &str, sent_bytes: u64, received_bytes: u64) {
         let mut connections = self.connection_metrics.write();
         if let Some(metrics) = connections.get_mut(connection_id) {
@@ -265,13 +324,24 @@ impl MetricsCollector {
         metrics.bytes_received = self.bytes_received.load(Ordering::Relaxed);
         
         let latencies = self.latency_measurements.read();
+        metrics.latency_sample_count = latencies.len() as u64;
         if !latencies.is_empty() {
             let latency_values: Vec<f64> = latencies.iter().map(|m| m.latency_ms).collect();
             metrics.latency_avg_ms = latency_values.iter().sum::<f64>() / latency_values.len() as f64;
             metrics.latency_min_ms = latency_values.iter().cloned().fold(f64::INFINITY, f64::min);
             metrics.latency_max_ms = latency_values.iter().cloned().fold(0.0, f64::max);
         }
-        
+
+        metrics.latency_p50_ms = self.latency_histogram.percentile(50.0);
+        metrics.latency_p90_ms = self.latency_histogram.percentile(90.0);
+        metrics.latency_p99_ms = self.latency_histogram.percentile(99.0);
+        metrics.latency_p999_ms = self.latency_histogram.percentile(99.9);
+
+        metrics.incoming_avg_bps = self.stats.incoming.rolling_avg_bps();
+        metrics.incoming_max_bps = self.stats.incoming.rolling_max_bps();
+        metrics.outgoing_avg_bps = self.stats.outgoing.rolling_avg_bps();
+        metrics.outgoing_max_bps = self.stats.outgoing.rolling_max_bps();
+
         let connections = self.connection_metrics.read();
         metrics.active_connections = connections.values().filter(|c| c.is_active).count() as u32;
         
@@ -322,6 +392,101 @@ impl MetricsCollector {
         self.historical_metrics.read().iter().cloned().collect()
     }
 
+    /// Aggregate the snapshots in `historical_metrics` that fall within `window` of the most
+    /// recent snapshot, turning the lifetime cumulative counters into a short-term trend:
+    /// bundle/byte deltas, `bundles_per_sec`/`throughput_bps` rates, and a `delivery_ratio`
+    /// computed from those deltas rather than lifetime totals. Returns a zeroed
+    /// `PerformanceMetrics` if fewer than two snapshots fall in the window, or if a counter in
+    /// the window's last snapshot is smaller than in its first — a smaller counter means a
+    /// `reset()` happened inside the window, and the delta would be meaningless.
+    pub fn aggregate_window(&self, window: Duration) -> PerformanceMetrics {
+        let historical = self.historical_metrics.read();
+        let mut result = PerformanceMetrics::new();
+
+        let latest = match historical.back() {
+            Some(latest) => latest,
+            None => return result,
+        };
+        result.timestamp = latest.timestamp;
+
+        let window = chrono::Duration::from_std(window).unwrap_or_else(|_| chrono::Duration::zero());
+        let cutoff = latest.timestamp - window;
+        let mut in_window = historical.iter().filter(|m| m.timestamp >= cutoff);
+
+        let first = match in_window.next() {
+            Some(first) => first,
+            None => return result,
+        };
+        let last = match in_window.last() {
+            Some(last) => last,
+            None => return result,
+        };
+
+        let elapsed = (last.timestamp - first.timestamp).to_std().unwrap_or(Duration::ZERO).as_secs_f64();
+        if elapsed <= 0.0 {
+            return result;
+        }
+
+        if last.bundles_sent < first.bundles_sent
+            || last.bundles_received < first.bundles_received
+            || last.bundles_forwarded < first.bundles_forwarded
+            || last.bundles_delivered < first.bundles_delivered
+            || last.bytes_sent < first.bytes_sent
+            || last.bytes_received < first.bytes_received
+        {
+            return result;
+        }
+
+        result.bundles_sent = last.bundles_sent - first.bundles_sent;
+        result.bundles_received = last.bundles_received - first.bundles_received;
+        result.bundles_forwarded = last.bundles_forwarded - first.bundles_forwarded;
+        result.bundles_delivered = last.bundles_delivered - first.bundles_delivered;
+        result.bundles_expired = last.bundles_expired.saturating_sub(first.bundles_expired);
+        result.bundles_dropped = last.bundles_dropped.saturating_sub(first.bundles_dropped);
+        result.bytes_sent = last.bytes_sent - first.bytes_sent;
+        result.bytes_received = last.bytes_received - first.bytes_received;
+
+        result.bundles_per_sec = (result.bundles_sent + result.bundles_forwarded) as f64 / elapsed;
+        result.throughput_bps = (result.bytes_sent + result.bytes_received) as f64 / elapsed;
+        result.compute_delivery_ratio();
+
+        result
+    }
+
+    /// A `(timestamp, bundles_per_sec)` series for charting, one point per pair of consecutive
+    /// historical snapshots at least `bucket` apart. Buckets whose span contains a `reset()`
+    /// (detected the same way as [`Self::aggregate_window`]) are skipped rather than reporting
+    /// a misleading rate.
+    pub fn rate_series(&self, bucket: Duration) -> Vec<(DateTime<Utc>, f64)> {
+        let historical = self.historical_metrics.read();
+        if historical.len() < 2 {
+            return Vec::new();
+        }
+
+        let bucket = chrono::Duration::from_std(bucket).unwrap_or_else(|_| chrono::Duration::zero());
+        let mut series = Vec::new();
+        let mut prev = &historical[0];
+
+        for current in historical.iter().skip(1) {
+            if current.timestamp - prev.timestamp < bucket {
+                continue;
+            }
+
+            let elapsed = (current.timestamp - prev.timestamp).to_std().unwrap_or(Duration::ZERO).as_secs_f64();
+            let reset_detected = current.bundles_sent < prev.bundles_sent || current.bundles_forwarded < prev.bundles_forwarded;
+
+            if elapsed > 0.0 && !reset_detected {
+                let sent_prev = prev.bundles_sent + prev.bundles_forwarded;
+                let sent_current = current.bundles_sent + current.bundles_forwarded;
+                series.push((current.timestamp, (sent_current - sent_prev) as f64 / elapsed));
+            }
+
+            prev = current;
+        }
+
+        series
+    }
+
     pub fn reset(&self) {
         self.bundles_sent.store(0, Ordering::Relaxed);
         self.bundles_received.store(0, Ordering::Relaxed);
@@ -333,6 +498,8 @@ impl MetricsCollector {
         self.bytes_received.store(0, Ordering::Relaxed);
         
         self.latency_measurements.write().clear();
+        self.latency_histogram.reset();
+        self.stats.reset();
         self.connection_metrics.write().clear();
         self.historical_metrics.write().clear();
     }
@@ -356,6 +523,68 @@ impl MetricsCollector {
         )
     }
 
+    /// Render the full counter/gauge set in Prometheus text exposition format, suitable for a
+    /// `/metrics` scrape endpoint. Counters are read straight off the `AtomicU64`s so they stay
+    /// monotonic across scrapes; the gauges and the histogram are derived values.
+    pub fn export_prometheus(&self) -> String {
+        let metrics = self.get_metrics();
+        let mut out = String::new();
+
+        macro_rules! counter {
+            ($name:expr, $help:expr, $value:expr) => {
+                out.push_str(&format!("# HELP {} {}\n# TYPE {} counter\n{} {}\n", $name, $help, $name, $name, $value));
+            };
+        }
+        macro_rules! gauge {
+            ($name:expr, $help:expr, $value:expr) => {
+                out.push_str(&format!("# HELP {} {}\n# TYPE {} gauge\n{} {}\n", $name, $help, $name, $name, $value));
+            };
+        }
+
+        counter!("bp_bundles_sent_total", "Total bundles sent", metrics.bundles_sent);
+        counter!("bp_bundles_received_total", "Total bundles received", metrics.bundles_received);
+        counter!("bp_bundles_forwarded_total", "Total bundles forwarded", metrics.bundles_forwarded);
+        counter!("bp_bundles_delivered_total", "Total bundles delivered", metrics.bundles_delivered);
+        counter!("bp_bundles_expired_total", "Total bundles expired before delivery", metrics.bundles_expired);
+        counter!("bp_bundles_dropped_total", "Total bundles dropped", metrics.bundles_dropped);
+        counter!("bp_bytes_sent_total", "Total bytes sent", metrics.bytes_sent);
+        counter!("bp_bytes_received_total", "Total bytes received", metrics.bytes_received);
+
+        gauge!("bp_delivery_ratio", "Delivered bundles over bundles sent or forwarded", metrics.delivery_ratio);
+        gauge!("bp_throughput_bps", "Lifetime average throughput in bytes/sec", metrics.throughput_bps);
+        gauge!("bp_incoming_avg_bps", "Rolling average inbound throughput in bytes/sec", metrics.incoming_avg_bps);
+        gauge!("bp_incoming_max_bps", "Rolling peak inbound throughput in bytes/sec", metrics.incoming_max_bps);
+        gauge!("bp_outgoing_avg_bps", "Rolling average outbound throughput in bytes/sec", metrics.outgoing_avg_bps);
+        gauge!("bp_outgoing_max_bps", "Rolling peak outbound throughput in bytes/sec", metrics.outgoing_max_bps);
+        gauge!("bp_active_connections", "Currently active CLA connections", metrics.active_connections);
+
+        out.push_str("# HELP bp_latency_ms Bundle delivery latency in milliseconds\n");
+        out.push_str("# TYPE bp_latency_ms histogram\n");
+        for boundary in PROMETHEUS_LATENCY_BUCKETS_MS {
+            let count = self.latency_histogram.count_at_or_below(boundary);
+            out.push_str(&format!("bp_latency_ms_bucket{{le=\"{}\"}} {}\n", boundary, count));
+        }
+        let total_count = self.latency_histogram.total_count();
+        out.push_str(&format!("bp_latency_ms_bucket{{le=\"+Inf\"}} {}\n", total_count));
+        out.push_str(&format!("bp_latency_ms_sum {}\n", self.latency_histogram.sum_ms()));
+        out.push_str(&format!("bp_latency_ms_count {}\n", total_count));
+
+        out.push_str("# HELP bp_connection_bytes_sent Bytes sent on a single CLA connection\n");
+        out.push_str("# TYPE bp_connection_bytes_sent counter\n");
+        out.push_str("# HELP bp_connection_bytes_received Bytes received on a single CLA connection\n");
+        out.push_str("# TYPE bp_connection_bytes_received counter\n");
+        out.push_str("# HELP bp_connection_active Whether a CLA connection is currently active\n");
+        out.push_str("# TYPE bp_connection_active gauge\n");
+        for conn in self.connection_metrics.read().values() {
+            let labels = format!("protocol=\"{}\",remote_address=\"{}\"", conn.protocol, conn.remote_address);
+            out.push_str(&format!("bp_connection_bytes_sent{{{}}} {}\n", labels, conn.bytes_sent));
+            out.push_str(&format!("bp_connection_bytes_received{{{}}} {}\n", labels, conn.bytes_received));
+            out.push_str(&format!("bp_connection_active{{{}}} {}\n", labels, conn.is_active as u8));
+        }
+
+        out
+    }
+
     pub fn get_summary(&self) -> String {
         let metrics = self.get_metrics();
         format!(
@@ -409,14 +638,16 @@ impl MetricsAggregator {
         }
 
         let mut aggregated = PerformanceMetrics::new();
-        let mut total_latency = 0.0;
-        let mut latency_count = 0;
+        // Weighted sum for the pooled mean Σ(avgᵢ·nᵢ)/Σnᵢ — averaging each collector's average
+        // with equal weight would badly skew the result when traffic volumes differ.
+        let mut weighted_latency = 0.0;
         let mut min_latency = f64::MAX;
         let mut max_latency = 0.0;
+        let mut histogram_counts = Vec::with_capacity(collectors.len());
 
         for collector in collectors.iter() {
             let metrics = collector.get_metrics();
-            
+
             aggregated.bundles_sent += metrics.bundles_sent;
             aggregated.bundles_received += metrics.bundles_received;
             aggregated.bundles_forwarded += metrics.bundles_forwarded;
@@ -425,26 +656,40 @@ impl MetricsAggregator {
             aggregated.bundles_dropped += metrics.bundles_dropped;
             aggregated.bytes_sent += metrics.bytes_sent;
             aggregated.bytes_received += metrics.bytes_received;
+            // Throughput is additive: each collector represents an independent link, so the
+            // combined throughput really is the sum, unlike latency which must be weighted.
             aggregated.throughput_bps += metrics.throughput_bps;
             aggregated.active_connections += metrics.active_connections;
-            
-            if metrics.latency_avg_ms > 0.0 {
-                total_latency += metrics.latency_avg_ms;
-                latency_count += 1;
-                
+            aggregated.incoming_avg_bps += metrics.incoming_avg_bps;
+            aggregated.incoming_max_bps += metrics.incoming_max_bps;
+            aggregated.outgoing_avg_bps += metrics.outgoing_avg_bps;
+            aggregated.outgoing_max_bps += metrics.outgoing_max_bps;
+
+            if metrics.latency_sample_count > 0 {
+                weighted_latency += metrics.latency_avg_ms * metrics.latency_sample_count as f64;
+                aggregated.latency_sample_count += metrics.latency_sample_count;
+
                 if metrics.latency_min_ms < min_latency {
                     min_latency = metrics.latency_min_ms;
                 }
                 if metrics.latency_max_ms > max_latency {
                     max_latency = metrics.latency_max_ms;
                 }
+
+                histogram_counts.push(collector.latency_histogram_counts());
             }
         }
 
-        if latency_count > 0 {
-            aggregated.latency_avg_ms = total_latency / latency_count as f64;
+        if aggregated.latency_sample_count > 0 {
+            aggregated.latency_avg_ms = weighted_latency / aggregated.latency_sample_count as f64;
             aggregated.latency_min_ms = min_latency;
             aggregated.latency_max_ms = max_latency;
+            // Merge the histograms rather than averaging each collector's own percentile:
+            // percentiles don't combine linearly, so averaging averages would be wrong.
+            aggregated.latency_p50_ms = histogram::percentile_of_merged(&histogram_counts, 50.0);
+            aggregated.latency_p90_ms = histogram::percentile_of_merged(&histogram_counts, 90.0);
+            aggregated.latency_p99_ms = histogram::percentile_of_merged(&histogram_counts, 99.0);
+            aggregated.latency_p999_ms = histogram::percentile_of_merged(&histogram_counts, 99.9);
         }
 
         aggregated.compute_delivery_ratio();
@@ -454,6 +699,396 @@ impl MetricsAggregator {
     }
 }
 
+/// A bounded-memory, O(1)-record / O(bucket count)-query latency histogram, modeled on
+/// HdrHistogram: values are bucketed on a log scale (bucket = position of the highest set
+/// bit) with a fixed number of linearly-spaced sub-buckets per octave for precision, so
+/// memory and query cost depend only on the trackable range and precision, never on sample
+/// count. Used by [`MetricsCollector`] to answer tail-latency percentile queries that a
+/// running average/min/max hides.
+mod histogram {
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    /// Values above this are clamped into the top bucket; 5 minutes comfortably covers DTN
+    /// link delays while keeping the bucket count small.
+    const HIGHEST_TRACKABLE_VALUE_MS: u64 = 5 * 60 * 1000;
+    /// ~3 significant figures of resolution within each octave.
+    const SUB_BUCKET_HALF_COUNT_MAGNITUDE: u32 = 10;
+    const SUB_BUCKET_COUNT: u64 = 1 << (SUB_BUCKET_HALF_COUNT_MAGNITUDE + 1);
+    const SUB_BUCKET_HALF_COUNT: u64 = SUB_BUCKET_COUNT / 2;
+    const SUB_BUCKET_MASK: u64 = SUB_BUCKET_COUNT - 1;
+
+    fn bucket_count() -> u32 {
+        let mut smallest_untrackable_value = SUB_BUCKET_COUNT;
+        let mut count = 1u32;
+        while smallest_untrackable_value <= HIGHEST_TRACKABLE_VALUE_MS {
+            smallest_untrackable_value <<= 1;
+            count += 1;
+        }
+        count
+    }
+
+    fn counts_len() -> usize {
+        (SUB_BUCKET_COUNT + (bucket_count() as u64 - 1) * SUB_BUCKET_HALF_COUNT) as usize
+    }
+
+    /// Highest set bit position of `value | SUB_BUCKET_MASK`, minus the sub-bucket width, i.e.
+    /// which octave `value` falls in relative to the sub-bucket resolution.
+    fn bucket_index_for(value: u64) -> u32 {
+        let bit_length = 64 - (value | SUB_BUCKET_MASK).leading_zeros();
+        bit_length.saturating_sub(SUB_BUCKET_HALF_COUNT_MAGNITUDE + 1)
+    }
+
+    fn sub_bucket_index_for(value: u64, bucket_index: u32) -> u64 {
+        value >> bucket_index
+    }
+
+    fn counts_index(bucket_index: u32, sub_bucket_index: u64) -> usize {
+        if bucket_index == 0 {
+            sub_bucket_index as usize
+        } else {
+            let bucket_base_index =
+                SUB_BUCKET_COUNT + (bucket_index as u64 - 1) * SUB_BUCKET_HALF_COUNT;
+            (bucket_base_index + (sub_bucket_index - SUB_BUCKET_HALF_COUNT)) as usize
+        }
+    }
+
+    /// The representative (lowest-equivalent) value a given counts-array slot stands for.
+    fn value_for_index(index: usize) -> u64 {
+        if (index as u64) < SUB_BUCKET_COUNT {
+            index as u64
+        } else {
+            let bucket_index =
+                ((index as u64 - SUB_BUCKET_COUNT) / SUB_BUCKET_HALF_COUNT) as u32 + 1;
+            let bucket_base_index = SUB_BUCKET_COUNT + (bucket_index as u64 - 1) * SUB_BUCKET_HALF_COUNT;
+            let sub_bucket_index = (index as u64 - bucket_base_index) + SUB_BUCKET_HALF_COUNT;
+            sub_bucket_index << bucket_index
+        }
+    }
+
+    /// Merge several histograms' bucket-count snapshots (e.g. from
+    /// [`super::MetricsCollector::latency_histogram_counts`]) and compute the `p`th percentile
+    /// of the combined distribution. Correct where averaging each histogram's own percentile
+    /// would not be, since percentiles don't combine linearly.
+    pub fn percentile_of_merged(counts_list: &[Vec<u64>], p: f64) -> f64 {
+        let len = counts_len();
+        let mut merged = vec![0u64; len];
+        let mut total = 0u64;
+
+        for counts in counts_list {
+            for (index, &count) in counts.iter().enumerate().take(len) {
+                merged[index] += count;
+                total += count;
+            }
+        }
+
+        if total == 0 {
+            return 0.0;
+        }
+
+        let target = ((p.clamp(0.0, 100.0) / 100.0) * total as f64).ceil() as u64;
+        let mut running = 0u64;
+        for (index, &count) in merged.iter().enumerate() {
+            running += count;
+            if running >= target {
+                return value_for_index(index) as f64;
+            }
+        }
+
+        value_for_index(len - 1) as f64
+    }
+
+    #[derive(Debug)]
+    pub struct LatencyHistogram {
+        counts: Vec<AtomicU64>,
+        total_count: AtomicU64,
+        total_sum_ms: AtomicU64,
+    }
+
+    impl LatencyHistogram {
+        pub fn new() -> Self {
+            let len = counts_len();
+            Self {
+                counts: (0..len).map(|_| AtomicU64::new(0)).collect(),
+                total_count: AtomicU64::new(0),
+                total_sum_ms: AtomicU64::new(0),
+            }
+        }
+
+        /// Record one latency sample, in milliseconds. Out-of-range values are clamped into
+        /// the top bucket rather than rejected.
+        pub fn record(&self, latency_ms: f64) {
+            let value = (latency_ms.max(0.0) as u64).min(HIGHEST_TRACKABLE_VALUE_MS);
+            let bucket_index = bucket_index_for(value);
+            let sub_bucket_index = sub_bucket_index_for(value, bucket_index);
+            let index = counts_index(bucket_index, sub_bucket_index).min(self.counts.len() - 1);
+
+            self.counts[index].fetch_add(1, Ordering::Relaxed);
+            self.total_count.fetch_add(1, Ordering::Relaxed);
+            self.total_sum_ms.fetch_add(value, Ordering::Relaxed);
+        }
+
+        /// Count of recorded samples at or below `value_ms` (a Prometheus-style cumulative
+        /// histogram bucket count).
+        pub fn count_at_or_below(&self, value_ms: f64) -> u64 {
+            self.counts
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| value_for_index(*index) as f64 <= value_ms)
+                .map(|(_, count)| count.load(Ordering::Relaxed))
+                .sum()
+        }
+
+        /// Total number of samples ever recorded (since construction or the last [`Self::reset`]).
+        pub fn total_count(&self) -> u64 {
+            self.total_count.load(Ordering::Relaxed)
+        }
+
+        /// Sum, in milliseconds, of every recorded sample (clamped values count at the clamp).
+        pub fn sum_ms(&self) -> u64 {
+            self.total_sum_ms.load(Ordering::Relaxed)
+        }
+
+        /// A plain snapshot of the per-slot counts, for merging several histograms together
+        /// (all instances share the same bucket layout, so the slots line up directly).
+        pub fn counts_snapshot(&self) -> Vec<u64> {
+            self.counts.iter().map(|count| count.load(Ordering::Relaxed)).collect()
+        }
+
+        /// The `p`th percentile (0-100) of recorded values, in milliseconds. `O(bucket
+        /// count)`, independent of how many samples were ever recorded.
+        pub fn percentile(&self, p: f64) -> f64 {
+            let total = self.total_count.load(Ordering::Relaxed);
+            if total == 0 {
+                return 0.0;
+            }
+
+            let target = ((p.clamp(0.0, 100.0) / 100.0) * total as f64).ceil() as u64;
+            let mut running = 0u64;
+
+            for (index, count) in self.counts.iter().enumerate() {
+                running += count.load(Ordering::Relaxed);
+                if running >= target {
+                    return value_for_index(index) as f64;
+                }
+            }
+
+            value_for_index(self.counts.len() - 1) as f64
+        }
+
+        pub fn reset(&self) {
+            for count in &self.counts {
+                count.store(0, Ordering::Relaxed);
+            }
+            self.total_count.store(0, Ordering::Relaxed);
+            self.total_sum_ms.store(0, Ordering::Relaxed);
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_percentile_with_no_samples_is_zero() {
+            let histogram = LatencyHistogram::new();
+            assert_eq!(histogram.percentile(50.0), 0.0);
+        }
+
+        #[test]
+        fn test_percentiles_over_uniform_samples() {
+            let histogram = LatencyHistogram::new();
+            for value in 1..=1000u64 {
+                histogram.record(value as f64);
+            }
+
+            let p50 = histogram.percentile(50.0);
+            let p99 = histogram.percentile(99.0);
+            assert!((450.0..=560.0).contains(&p50), "p50 was {p50}");
+            assert!((970.0..=1010.0).contains(&p99), "p99 was {p99}");
+            assert!(p99 > p50);
+        }
+
+        #[test]
+        fn test_values_above_highest_trackable_are_clamped() {
+            let histogram = LatencyHistogram::new();
+            histogram.record(HIGHEST_TRACKABLE_VALUE_MS as f64 + 10_000.0);
+            let p99 = histogram.percentile(99.0);
+            assert!(p99 <= HIGHEST_TRACKABLE_VALUE_MS as f64);
+        }
+
+        #[test]
+        fn test_reset_clears_histogram() {
+            let histogram = LatencyHistogram::new();
+            histogram.record(42.0);
+            histogram.reset();
+            assert_eq!(histogram.percentile(50.0), 0.0);
+        }
+    }
+}
+
+/// Rolling-window bandwidth accounting: tracks recent throughput separately from
+/// [`PerformanceMetrics::throughput_bps`]'s lifetime average, so the reported
+/// `incoming_*`/`outgoing_*` figures follow current link conditions instead of being dragged
+/// down by a link's entire history. No routing engine in this crate reads them.
+mod bandwidth {
+    use parking_lot::Mutex;
+    use std::collections::VecDeque;
+    use std::time::{Duration, Instant};
+
+    /// Number of completed intervals kept in the ring.
+    const WINDOW_SLOTS: usize = 10;
+    /// Length of one interval; the window covers `WINDOW_SLOTS * INTERVAL` of history.
+    const INTERVAL: Duration = Duration::from_secs(1);
+
+    struct RollingWindow {
+        /// Byte counts for the last `WINDOW_SLOTS` completed intervals, oldest first.
+        completed: VecDeque<u64>,
+        /// Bytes observed so far in the interval that hasn't completed yet.
+        current: u64,
+        /// When the current interval started.
+        interval_start: Instant,
+    }
+
+    /// Rolling average/peak throughput for one direction (inbound or outbound), backed by a
+    /// fixed-size ring buffer of per-interval byte counts.
+    pub struct RollingBandwidth {
+        window: Mutex<RollingWindow>,
+    }
+
+    impl RollingBandwidth {
+        pub fn new() -> Self {
+            Self {
+                window: Mutex::new(RollingWindow {
+                    completed: VecDeque::with_capacity(WINDOW_SLOTS),
+                    current: 0,
+                    interval_start: Instant::now(),
+                }),
+            }
+        }
+
+        pub fn record(&self, bytes: u64) {
+            let mut window = self.window.lock();
+            Self::advance(&mut window);
+            window.current += bytes;
+        }
+
+        /// Roll over any whole intervals that have elapsed since the last record/read,
+        /// zero-filling intervals that saw no traffic at all so idle periods correctly drag
+        /// the rolling average down rather than being skipped over.
+        fn advance(window: &mut RollingWindow) {
+            let elapsed = window.interval_start.elapsed();
+            let intervals_passed = (elapsed.as_secs_f64() / INTERVAL.as_secs_f64()).floor() as u64;
+            if intervals_passed == 0 {
+                return;
+            }
+
+            let completed_now = std::mem::take(&mut window.current);
+            Self::push(&mut window.completed, completed_now);
+            for _ in 1..intervals_passed.min(WINDOW_SLOTS as u64 + 1) {
+                Self::push(&mut window.completed, 0);
+            }
+
+            window.interval_start += INTERVAL * intervals_passed as u32;
+        }
+
+        fn push(completed: &mut VecDeque<u64>, value: u64) {
+            if completed.len() >= WINDOW_SLOTS {
+                completed.pop_front();
+            }
+            completed.push_back(value);
+        }
+
+        pub fn rolling_avg_bps(&self) -> f64 {
+            let mut window = self.window.lock();
+            Self::advance(&mut window);
+            let total: u64 = window.completed.iter().sum();
+            total as f64 / (WINDOW_SLOTS as f64 * INTERVAL.as_secs_f64())
+        }
+
+        pub fn rolling_max_bps(&self) -> f64 {
+            let mut window = self.window.lock();
+            Self::advance(&mut window);
+            let peak = window.completed.iter().copied().max().unwrap_or(0);
+            peak as f64 / INTERVAL.as_secs_f64()
+        }
+
+        pub fn reset(&self) {
+            let mut window = self.window.lock();
+            window.completed.clear();
+            window.current = 0;
+            window.interval_start = Instant::now();
+        }
+    }
+
+    /// Inbound and outbound rolling bandwidth, tracked separately since a DTN link's upload
+    /// and download conditions can differ substantially.
+    pub struct StatsAccounting {
+        pub incoming: RollingBandwidth,
+        pub outgoing: RollingBandwidth,
+    }
+
+    impl StatsAccounting {
+        pub fn new() -> Self {
+            Self {
+                incoming: RollingBandwidth::new(),
+                outgoing: RollingBandwidth::new(),
+            }
+        }
+
+        pub fn record_incoming(&self, bytes: u64) {
+            self.incoming.record(bytes);
+        }
+
+        pub fn record_outgoing(&self, bytes: u64) {
+            self.outgoing.record(bytes);
+        }
+
+        pub fn reset(&self) {
+            self.incoming.reset();
+            self.outgoing.reset();
+        }
+    }
+
+    impl std::fmt::Debug for StatsAccounting {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("StatsAccounting")
+                .field("incoming_avg_bps", &self.incoming.rolling_avg_bps())
+                .field("outgoing_avg_bps", &self.outgoing.rolling_avg_bps())
+                .finish()
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_rolling_avg_accumulates_within_interval() {
+            let bandwidth = RollingBandwidth::new();
+            bandwidth.record(1000);
+            bandwidth.record(2000);
+            // Still within the first (incomplete) interval, so nothing has rolled into the
+            // window yet and the average is 0.
+            assert_eq!(bandwidth.rolling_avg_bps(), 0.0);
+        }
+
+        #[test]
+        fn test_fresh_bandwidth_reports_zero() {
+            let bandwidth = RollingBandwidth::new();
+            assert_eq!(bandwidth.rolling_avg_bps(), 0.0);
+            assert_eq!(bandwidth.rolling_max_bps(), 0.0);
+        }
+
+        #[test]
+        fn test_reset_clears_window() {
+            let bandwidth = RollingBandwidth::new();
+            bandwidth.record(5000);
+            bandwidth.reset();
+            assert_eq!(bandwidth.rolling_avg_bps(), 0.0);
+        }
+    }
+}
+
 impl Default for MetricsAggregator {
     fn default() -> Self {
         Self::new()
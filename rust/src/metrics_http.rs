@@ -0,0 +1,45 @@
+//! Minimal embedded HTTP endpoint serving [`MetricsCollector::export_prometheus`] at
+//! `/metrics`, gated behind the `metrics-http` feature. It answers over plain tokio sockets
+//! and uses no HTTP server crate.
+//!
+//! This responds the same way to any request (method and path are ignored) rather than
+//! implementing real routing, since serving one fixed document is all a metrics endpoint
+//! needs.
+
+use crate::error::{BpError, BpResult};
+use crate::metrics::MetricsCollector;
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+/// Bind `local_address` and serve `collector`'s Prometheus text exposition on every
+/// connection, looping until the listener errors. Intended to be spawned as a background task
+/// alongside the node's CLAs.
+pub async fn serve_metrics(local_address: &str, collector: Arc<MetricsCollector>) -> BpResult<()> {
+    let listener = TcpListener::bind(local_address)
+        .await
+        .map_err(|e| BpError::Protocol(format!("failed to bind metrics endpoint: {}", e)))?;
+
+    loop {
+        let (stream, _) = listener
+            .accept()
+            .await
+            .map_err(|e| BpError::Protocol(format!("metrics endpoint accept failed: {}", e)))?;
+        tokio::spawn(handle_connection(stream, collector.clone()));
+    }
+}
+
+async fn handle_connection(mut stream: tokio::net::TcpStream, collector: Arc<MetricsCollector>) {
+    let mut buf = [0u8; 1024];
+    if stream.read(&mut buf).await.is_err() {
+        return;
+    }
+
+    let body = collector.export_prometheus();
+    let response = format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        body.len(),
+        body
+    );
+    let _ = stream.write_all(response.as_bytes()).await;
+}
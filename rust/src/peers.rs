@@ -0,0 +1,200 @@
+//! Neighbor discovery for a full-mesh view of reachable peers.
+//!
+//! [`PeerManager`] replaces ad-hoc address string munging in [`ClaManager`](crate::cla::ClaManager)
+//! with a table of known neighbors, each reachable over one or more `(protocol, address)`
+//! pairs, kept alive by periodic hello bundles built with [`hello_bundle`]. A driver loop is
+//! expected to call [`PeerManager::due_for_probe`] to know who to send a hello to next, and
+//! [`PeerManager::sweep_liveness`] to age out peers that have gone quiet, reacting to the
+//! returned [`PeerEvent`]s (e.g. retrying the [`BundleStore`](crate::store::BundleStore) the
+//! moment a peer reconnects).
+
+use crate::types::{Bundle, Eid};
+use chrono::{DateTime, Utc};
+use parking_lot::RwLock;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// How long since last contact before a peer is considered stale (still reported, and still
+/// returned by `reachable_clas` like a live peer) or dead (dropped from `reachable_clas`).
+const STALE_AFTER_SECS: i64 = 60;
+const DEAD_AFTER_SECS: i64 = 300;
+
+/// A neighbor's liveness, inferred from how recently it was last heard from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Liveness {
+    Live,
+    Stale,
+    Dead,
+}
+
+/// What we know about a neighbor: when it was last heard from and which CLAs can reach it.
+#[derive(Debug, Clone)]
+pub struct NeighborRecord {
+    pub eid: Eid,
+    pub last_seen: DateTime<Utc>,
+    pub reachable: Vec<(String, String)>,
+    pub liveness: Liveness,
+}
+
+/// Emitted by [`PeerManager::record_seen`] when a stale or dead neighbor is heard from again,
+/// and by [`PeerManager::sweep_liveness`] when a neighbor turns dead, so callers can react
+/// (e.g. trigger store-and-forward retries). A neighbor aging from live to stale emits nothing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PeerEvent {
+    Connected(Eid),
+    Disconnected(Eid),
+}
+
+/// Table of known neighbors and their reachability, maintained by periodic hello bundles.
+#[derive(Debug, Default)]
+pub struct PeerManager {
+    peers: RwLock<HashMap<Eid, NeighborRecord>>,
+}
+
+impl PeerManager {
+    pub fn new() -> Self {
+        Self { peers: RwLock::new(HashMap::new()) }
+    }
+
+    /// Learn (or refresh) a `(protocol, address)` pair a peer is reachable over, marking it
+    /// seen now.
+    pub fn add_peer(&self, eid: Eid, protocol: impl Into<String>, address: impl Into<String>) {
+        let protocol = protocol.into();
+        let address = address.into();
+        let mut peers = self.peers.write();
+
+        let record = peers.entry(eid.clone()).or_insert_with(|| NeighborRecord {
+            eid,
+            last_seen: Utc::now(),
+            reachable: Vec::new(),
+            liveness: Liveness::Live,
+        });
+
+        if !record.reachable.iter().any(|(p, a)| *p == protocol && *a == address) {
+            record.reachable.push((protocol, address));
+        }
+        record.last_seen = Utc::now();
+        record.liveness = Liveness::Live;
+    }
+
+    /// Mark a peer as heard from right now (e.g. on receiving a hello reply or any bundle from
+    /// it), returning a [`PeerEvent::Connected`] if it was previously stale or dead.
+    pub fn record_seen(&self, eid: &Eid) -> Option<PeerEvent> {
+        let mut peers = self.peers.write();
+        let record = peers.get_mut(eid)?;
+        let was_down = record.liveness != Liveness::Live;
+        record.last_seen = Utc::now();
+        record.liveness = Liveness::Live;
+        was_down.then(|| PeerEvent::Connected(eid.clone()))
+    }
+
+    /// All known neighbor records.
+    pub fn list_peers(&self) -> Vec<NeighborRecord> {
+        self.peers.read().values().cloned().collect()
+    }
+
+    /// `(protocol, address)` pairs a live or stale peer can currently be reached over; empty
+    /// once the peer is [`Liveness::Dead`].
+    pub fn reachable_clas(&self, eid: &Eid) -> Vec<(String, String)> {
+        self.peers
+            .read()
+            .get(eid)
+            .filter(|record| record.liveness != Liveness::Dead)
+            .map(|record| record.reachable.clone())
+            .unwrap_or_default()
+    }
+
+    /// Neighbors that haven't been heard from in at least `probe_interval` and so are due for
+    /// another hello bundle.
+    pub fn due_for_probe(&self, probe_interval: Duration) -> Vec<Eid> {
+        let now = Utc::now();
+        let threshold = chrono::Duration::from_std(probe_interval).unwrap_or_else(|_| chrono::Duration::zero());
+        self.peers
+            .read()
+            .values()
+            .filter(|record| now - record.last_seen >= threshold)
+            .map(|record| record.eid.clone())
+            .collect()
+    }
+
+    /// Age every neighbor's liveness based on time since last seen, returning a
+    /// [`PeerEvent::Disconnected`] for each peer that just crossed into [`Liveness::Dead`].
+    pub fn sweep_liveness(&self) -> Vec<PeerEvent> {
+        let now = Utc::now();
+        let mut events = Vec::new();
+
+        for record in self.peers.write().values_mut() {
+            let since_seen = now - record.last_seen;
+            let next = if since_seen >= chrono::Duration::seconds(DEAD_AFTER_SECS) {
+                Liveness::Dead
+            } else if since_seen >= chrono::Duration::seconds(STALE_AFTER_SECS) {
+                Liveness::Stale
+            } else {
+                Liveness::Live
+            };
+
+            if next == Liveness::Dead && record.liveness != Liveness::Dead {
+                events.push(PeerEvent::Disconnected(record.eid.clone()));
+            }
+            record.liveness = next;
+        }
+
+        events
+    }
+}
+
+/// A lightweight keepalive bundle used to probe a neighbor's liveness. Carries no meaningful
+/// payload; its arrival alone is the signal.
+pub fn hello_bundle(local_eid: &Eid, peer_eid: &Eid) -> Bundle {
+    Bundle::new(local_eid.clone(), peer_eid.clone(), "HELLO")
+        .with_ttl(Duration::from_secs(30))
+        .add_metadata("bp-control", "hello")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eid(s: &str) -> Eid {
+        Eid::new(s).unwrap()
+    }
+
+    #[test]
+    fn test_add_peer_and_reachable_clas() {
+        let manager = PeerManager::new();
+        manager.add_peer(eid("ipn:2.1"), "tcp", "127.0.0.1:4556");
+
+        let reachable = manager.reachable_clas(&eid("ipn:2.1"));
+        assert_eq!(reachable, vec![("tcp".to_string(), "127.0.0.1:4556".to_string())]);
+        assert_eq!(manager.list_peers().len(), 1);
+    }
+
+    #[test]
+    fn test_record_seen_emits_connected_after_disconnect() {
+        let manager = PeerManager::new();
+        manager.add_peer(eid("ipn:2.1"), "tcp", "127.0.0.1:4556");
+
+        {
+            let mut peers = manager.peers.write();
+            let record = peers.get_mut(&eid("ipn:2.1")).unwrap();
+            record.last_seen = Utc::now() - chrono::Duration::seconds(DEAD_AFTER_SECS + 1);
+        }
+
+        let events = manager.sweep_liveness();
+        assert_eq!(events, vec![PeerEvent::Disconnected(eid("ipn:2.1"))]);
+        assert!(manager.reachable_clas(&eid("ipn:2.1")).is_empty());
+
+        let reconnect = manager.record_seen(&eid("ipn:2.1"));
+        assert_eq!(reconnect, Some(PeerEvent::Connected(eid("ipn:2.1"))));
+        assert_eq!(manager.reachable_clas(&eid("ipn:2.1")).len(), 1);
+    }
+
+    #[test]
+    fn test_due_for_probe_honors_interval() {
+        let manager = PeerManager::new();
+        manager.add_peer(eid("ipn:2.1"), "tcp", "127.0.0.1:4556");
+
+        assert!(manager.due_for_probe(Duration::from_secs(3600)).is_empty());
+        assert_eq!(manager.due_for_probe(Duration::from_secs(0)), vec![eid("ipn:2.1")]);
+    }
+}
@@ -0,0 +1,287 @@
+//! QUIC convergence layer, built on `quinn`/`rustls`.
+//!
+//! Unlike [`crate::cla::TcpCla`] (one bundle-at-a-time over a single negotiated TCPCL
+//! session) or [`crate::cla::UdpCla`] (one bundle per best-effort datagram), [`QuicCla`]
+//! gives each bundle its own unidirectional QUIC stream: several bundles to the same peer
+//! can be in flight concurrently without one large bundle head-of-line blocking the rest,
+//! while still getting QUIC's built-in congestion control and loss recovery that raw UDP
+//! lacks. QUIC connections also survive the peer's address changing mid-session (connection
+//! migration), which matters for intermittently-connected DTN links more than for a typical
+//! always-on TCP peer.
+
+use crate::{
+    backoff::BackoffPolicy,
+    cla::{default_retry_policy, Cla, ConnectionState},
+    codec::{codec_for, BundleCodec},
+    dns::{DnsResolver, SystemResolver},
+    error::{BpError, BpResult},
+    tls::{self, TlsProfile},
+    types::{Bundle, TransportConfig},
+};
+use async_trait::async_trait;
+use bytes::Bytes;
+use parking_lot::RwLock;
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    },
+};
+use tokio::sync::mpsc;
+
+/// QUIC-based Convergence Layer Adapter. Always TLS-secured (QUIC has no cleartext mode),
+/// so `config` must carry `with_tls(...)` parameters or [`QuicCla::new`] fails.
+pub struct QuicCla {
+    config: TransportConfig,
+    codec: Arc<dyn BundleCodec>,
+    tls_profile: TlsProfile,
+    endpoint: RwLock<Option<quinn::Endpoint>>,
+    /// Live outbound connections keyed by peer address, reused across sends and dropped on
+    /// send failure so the next send redials instead of opening a stream on a dead
+    /// connection.
+    connections: RwLock<HashMap<String, quinn::Connection>>,
+    receive_callback: RwLock<Option<Arc<dyn Fn(Bytes, String) + Send + Sync>>>,
+    shutdown_tx: RwLock<Option<mpsc::Sender<()>>>,
+    conn_state: Arc<RwLock<ConnectionState>>,
+    retry_count: Arc<AtomicU32>,
+    retry_policy: BackoffPolicy,
+    resolver: Arc<dyn DnsResolver>,
+}
+
+impl Debug for QuicCla {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("QuicCla")
+            .field("config", &self.config)
+            .field("codec", &self.codec.name())
+            .field("endpoint", &self.endpoint.read().is_some())
+            .field("connections", &"<connections>")
+            .field("receive_callback", &"<callback>")
+            .field("conn_state", &self.conn_state)
+            .field("retry_count", &self.retry_count.load(Ordering::Relaxed))
+            .finish()
+    }
+}
+
+impl QuicCla {
+    pub fn new(config: TransportConfig) -> BpResult<Self> {
+        Self::with_retry_policy(config, default_retry_policy())
+    }
+
+    /// Like [`Self::new`], but with a caller-supplied backoff schedule. `QuicCla` stores it
+    /// but runs no health check: nothing rebinds the endpoint after it fails, and
+    /// [`ConnectionState`] stays `Connected`.
+    pub fn with_retry_policy(config: TransportConfig, retry_policy: BackoffPolicy) -> BpResult<Self> {
+        Self::with_resolver(config, retry_policy, Arc::new(SystemResolver))
+    }
+
+    /// Like [`Self::with_retry_policy`], but with a caller-supplied [`DnsResolver`] for
+    /// outbound `dest_addr`s that aren't literal socket addresses.
+    pub fn with_resolver(
+        config: TransportConfig,
+        retry_policy: BackoffPolicy,
+        resolver: Arc<dyn DnsResolver>,
+    ) -> BpResult<Self> {
+        if config.protocol != "quic" {
+            return Err(BpError::InvalidArgs);
+        }
+
+        let codec = codec_for(&config.codec)?;
+        let tls_profile = TlsProfile::from_config(&config)
+            .ok_or_else(|| BpError::TlsHandshake("QuicCla requires with_tls(...) configuration".to_string()))?;
+
+        Ok(Self {
+            config,
+            codec,
+            tls_profile,
+            endpoint: RwLock::new(None),
+            connections: RwLock::new(HashMap::new()),
+            receive_callback: RwLock::new(None),
+            shutdown_tx: RwLock::new(None),
+            conn_state: Arc::new(RwLock::new(ConnectionState::Connected)),
+            retry_count: Arc::new(AtomicU32::new(0)),
+            retry_policy,
+            resolver,
+        })
+    }
+
+    /// Encode `bundle` with this CLA's configured codec and send it on its own stream to
+    /// `dest_addr`.
+    pub async fn send_bundle(&self, dest_addr: &str, bundle: &Bundle) -> BpResult<()> {
+        let encoded = self.codec.encode(bundle)?;
+        self.send(dest_addr, encoded).await
+    }
+
+    /// Decode a reassembled stream's contents with this CLA's configured codec.
+    pub fn deserialize_bundle(&self, data: &[u8]) -> BpResult<Bundle> {
+        self.codec.decode(data)
+    }
+
+    fn server_config(&self) -> BpResult<quinn::ServerConfig> {
+        let crypto = tls::build_server_crypto(&self.tls_profile)?;
+        Ok(quinn::ServerConfig::with_crypto(Arc::new(crypto)))
+    }
+
+    fn client_config(&self) -> BpResult<quinn::ClientConfig> {
+        let crypto = tls::build_client_crypto(&self.tls_profile)?;
+        Ok(quinn::ClientConfig::new(Arc::new(crypto)))
+    }
+
+    /// Reuse a pooled connection to `dest_addr` if one is still open, otherwise dial a
+    /// fresh one and pool it for future sends.
+    async fn get_connection(&self, dest_addr: &str) -> BpResult<quinn::Connection> {
+        if let Some(conn) = self.connections.read().get(dest_addr).cloned() {
+            if conn.close_reason().is_none() {
+                return Ok(conn);
+            }
+        }
+
+        let addr = self.resolver.resolve(dest_addr).await?;
+        let endpoint = self.endpoint.read().clone().ok_or(BpError::NotInitialized)?;
+
+        let server_name = self.tls_profile.server_name.clone().unwrap_or_else(|| addr.ip().to_string());
+        let mut connecting = endpoint
+            .connect_with(self.client_config()?, addr, &server_name)
+            .map_err(|e| BpError::Protocol(format!("failed to start QUIC connection: {}", e)))?;
+        let conn = (&mut connecting)
+            .await
+            .map_err(|e| BpError::Protocol(format!("QUIC handshake failed: {}", e)))?;
+
+        self.connections.write().insert(dest_addr.to_string(), conn.clone());
+        Ok(conn)
+    }
+
+    /// Read one incoming connection's unidirectional streams to completion, handing each
+    /// fully-read stream to the receive callback as one bundle. Runs until the peer closes
+    /// the connection.
+    async fn handle_connection(
+        connection: quinn::Connection,
+        callback: Arc<dyn Fn(Bytes, String) + Send + Sync>,
+        max_payload_size: usize,
+    ) {
+        let peer_addr = connection.remote_address().to_string();
+        loop {
+            let mut recv_stream = match connection.accept_uni().await {
+                Ok(stream) => stream,
+                Err(_) => break,
+            };
+
+            let callback = callback.clone();
+            let peer_addr = peer_addr.clone();
+            tokio::spawn(async move {
+                if let Ok(data) = recv_stream.read_to_end(max_payload_size).await {
+                    callback(Bytes::from(data), peer_addr);
+                }
+            });
+        }
+    }
+
+    async fn accept_loop(
+        endpoint: quinn::Endpoint,
+        callback: Arc<dyn Fn(Bytes, String) + Send + Sync>,
+        max_payload_size: usize,
+        mut shutdown_rx: mpsc::Receiver<()>,
+    ) {
+        loop {
+            tokio::select! {
+                incoming = endpoint.accept() => {
+                    let Some(connecting) = incoming else { break };
+                    let callback = callback.clone();
+                    tokio::spawn(async move {
+                        if let Ok(connection) = connecting.await {
+                            Self::handle_connection(connection, callback, max_payload_size).await;
+                        }
+                    });
+                }
+                _ = shutdown_rx.recv() => break,
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Cla for QuicCla {
+    fn protocol(&self) -> &str {
+        &self.config.protocol
+    }
+
+    fn local_address(&self) -> &str {
+        &self.config.local_address
+    }
+
+    fn max_payload_size(&self) -> usize {
+        self.config.max_payload_size
+    }
+
+    async fn start(&self) -> BpResult<()> {
+        let addr = self.resolver.resolve(&self.config.local_address).await?;
+
+        let endpoint = quinn::Endpoint::server(self.server_config()?, addr)
+            .map_err(|e| BpError::Protocol(format!("failed to bind QUIC endpoint: {}", e)))?;
+
+        *self.endpoint.write() = Some(endpoint.clone());
+        *self.conn_state.write() = ConnectionState::Connected;
+        self.retry_count.store(0, Ordering::Relaxed);
+
+        let callback = self.receive_callback.read().as_ref().cloned();
+        if let Some(callback) = callback {
+            let (shutdown_tx, shutdown_rx) = mpsc::channel(1);
+            *self.shutdown_tx.write() = Some(shutdown_tx);
+
+            tokio::spawn(Self::accept_loop(endpoint, callback, self.config.max_payload_size, shutdown_rx));
+        }
+
+        Ok(())
+    }
+
+    async fn stop(&self) -> BpResult<()> {
+        let shutdown_tx = self.shutdown_tx.write().take();
+        if let Some(tx) = shutdown_tx {
+            let _ = tx.send(()).await;
+        }
+
+        if let Some(endpoint) = self.endpoint.write().take() {
+            endpoint.close(0u32.into(), b"cla stopped");
+        }
+        self.connections.write().clear();
+        Ok(())
+    }
+
+    async fn send(&self, dest_addr: &str, data: Bytes) -> BpResult<()> {
+        let conn = self.get_connection(dest_addr).await?;
+
+        let result = async {
+            let mut send_stream = conn
+                .open_uni()
+                .await
+                .map_err(|e| BpError::Protocol(format!("failed to open QUIC stream: {}", e)))?;
+            send_stream
+                .write_all(&data)
+                .await
+                .map_err(|e| BpError::Protocol(format!("QUIC stream write failed: {}", e)))?;
+            send_stream
+                .finish()
+                .await
+                .map_err(|e| BpError::Protocol(format!("QUIC stream finish failed: {}", e)))
+        }
+        .await;
+
+        if result.is_err() {
+            self.connections.write().remove(dest_addr);
+        }
+        result
+    }
+
+    fn set_receive_callback(&self, callback: Arc<dyn Fn(Bytes, String) + Send + Sync>) {
+        *self.receive_callback.write() = Some(callback);
+    }
+
+    fn connection_state(&self) -> ConnectionState {
+        *self.conn_state.read()
+    }
+
+    fn retry_count(&self) -> u32 {
+        self.retry_count.load(Ordering::Relaxed)
+    }
+}
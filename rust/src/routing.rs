@@ -1,11 +1,13 @@
 use crate::{
     error::{BpError, BpResult},
-    types::{Bundle, Contact, Eid, Route},
+    types::{Bundle, Contact, Eid, Range, Route},
 };
 use chrono::{DateTime, Utc};
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering as AtomicOrdering};
 use std::sync::Arc;
 use uuid::Uuid;
 
@@ -14,6 +16,17 @@ pub trait RoutingEngine: Send + Sync {
     fn compute_routes(&self, dest_eid: &Eid, contacts: &[Contact]) -> Vec<Route>;
     fn update_contact(&self, contact: Contact);
     fn should_forward(&self, bundle: &Bundle, contact: &Contact) -> bool;
+    /// Per-engine telemetry as `(metric_name, value)` pairs, aggregated by
+    /// [`RoutingManager::metrics_text`] under the active engine's name as a label.
+    fn metrics(&self) -> Vec<(String, f64)>;
+
+    /// Find the single best route to `dest_eid` for a bundle of `bundle_size` bytes, searching
+    /// no earlier than `now`. Only [`ContactGraphRouting`] has a time-varying [`ContactPlan`] to
+    /// search against; other engines ignore `bundle_size`/`now` and fall back to their normal
+    /// [`Self::compute_routes`] over an empty contact snapshot.
+    fn find_route(&self, dest_eid: &Eid, _bundle_size: usize, _now: DateTime<Utc>) -> Option<Route> {
+        self.compute_routes(dest_eid, &[]).into_iter().next()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -37,10 +50,183 @@ impl RoutingContext {
     }
 }
 
+/// Persists per-bundle [`RoutingContext`]s and PROPHET delivery-probability tables across
+/// restarts, so an intermittently-powered node doesn't lose copy accounting or learned
+/// predictabilities every time it reboots. `namespace` separates engines sharing one store
+/// (e.g. "epidemic" vs. "spray_and_wait") so their contexts don't collide on bundle id.
+pub trait RoutingStore: Send + Sync {
+    /// Load every previously persisted context under `namespace`, e.g. at engine construction.
+    fn load_contexts(&self, namespace: &str) -> Vec<RoutingContext>;
+
+    /// Write through a single context's latest state.
+    fn persist_context(&self, namespace: &str, context: &RoutingContext);
+
+    /// Load a previously persisted PROPHET delivery-probability table for `namespace`.
+    fn load_probabilities(&self, namespace: &str) -> HashMap<Eid, f64>;
+
+    /// Write through the full delivery-probability table for `namespace`.
+    fn persist_probabilities(&self, namespace: &str, table: &HashMap<Eid, f64>);
+}
+
+/// How often a [`RoutingStore`]-backed engine writes through on mutation, trading immediacy
+/// for not blocking the forwarding hot path on disk I/O.
+#[derive(Debug, Clone, Copy)]
+pub enum FlushPolicy {
+    /// Persist synchronously on every mutation.
+    Immediate,
+    /// Persist only once this many mutations have accumulated since the last flush.
+    EveryN(u32),
+    /// Persist only if at least this much time has passed since the last flush.
+    Interval(chrono::Duration),
+}
+
+impl Default for FlushPolicy {
+    fn default() -> Self {
+        FlushPolicy::Immediate
+    }
+}
+
+/// Default file-backed [`RoutingStore`]: one JSON file per context under
+/// `<root>/<namespace>/contexts/`, and one JSON file per namespace's probability table under
+/// `<root>/<namespace>/probabilities.json`.
+#[derive(Debug)]
+pub struct FileRoutingStore {
+    root: std::path::PathBuf,
+}
+
+impl FileRoutingStore {
+    pub fn new(root: impl Into<std::path::PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn contexts_dir(&self, namespace: &str) -> std::path::PathBuf {
+        self.root.join(namespace).join("contexts")
+    }
+
+    fn probabilities_path(&self, namespace: &str) -> std::path::PathBuf {
+        self.root.join(namespace).join("probabilities.json")
+    }
+}
+
+impl RoutingStore for FileRoutingStore {
+    fn load_contexts(&self, namespace: &str) -> Vec<RoutingContext> {
+        let dir = self.contexts_dir(namespace);
+        match std::fs::read_dir(&dir) {
+            Ok(entries) => entries
+                .filter_map(|entry| entry.ok())
+                .filter_map(|entry| std::fs::read(entry.path()).ok())
+                .filter_map(|data| serde_json::from_slice(&data).ok())
+                .collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    fn persist_context(&self, namespace: &str, context: &RoutingContext) {
+        let dir = self.contexts_dir(namespace);
+        if std::fs::create_dir_all(&dir).is_err() {
+            return;
+        }
+        if let Ok(json) = serde_json::to_vec(context) {
+            let path = dir.join(format!("{}.json", context.bundle_id));
+            std::fs::write(path, json).ok();
+        }
+    }
+
+    fn load_probabilities(&self, namespace: &str) -> HashMap<Eid, f64> {
+        std::fs::read(self.probabilities_path(namespace))
+            .ok()
+            .and_then(|data| serde_json::from_slice(&data).ok())
+            .unwrap_or_default()
+    }
+
+    fn persist_probabilities(&self, namespace: &str, table: &HashMap<Eid, f64>) {
+        let path = self.probabilities_path(namespace);
+        if let Some(parent) = path.parent() {
+            if std::fs::create_dir_all(parent).is_err() {
+                return;
+            }
+        }
+        if let Ok(json) = serde_json::to_vec(table) {
+            std::fs::write(path, json).ok();
+        }
+    }
+}
+
+/// Write-through helper shared by every persistable engine: decides, per [`FlushPolicy`],
+/// whether a given mutation should actually hit the [`RoutingStore`] now or be coalesced with
+/// later ones. A `persistence` with no store configured is a no-op, so engines can hold one
+/// unconditionally instead of branching on `Option` at every call site.
+struct Persistence {
+    store: Option<Arc<dyn RoutingStore>>,
+    namespace: String,
+    policy: FlushPolicy,
+    pending: AtomicU64,
+    last_flush: RwLock<DateTime<Utc>>,
+}
+
+impl Persistence {
+    fn none(namespace: &str) -> Self {
+        Self {
+            store: None,
+            namespace: namespace.to_string(),
+            policy: FlushPolicy::Immediate,
+            pending: AtomicU64::new(0),
+            last_flush: RwLock::new(Utc::now()),
+        }
+    }
+
+    fn with_store(namespace: &str, store: Arc<dyn RoutingStore>, policy: FlushPolicy) -> Self {
+        Self {
+            store: Some(store),
+            namespace: namespace.to_string(),
+            policy,
+            pending: AtomicU64::new(0),
+            last_flush: RwLock::new(Utc::now()),
+        }
+    }
+
+    fn store(&self) -> Option<&Arc<dyn RoutingStore>> {
+        self.store.as_ref()
+    }
+
+    fn due(&self) -> bool {
+        match self.policy {
+            FlushPolicy::Immediate => true,
+            FlushPolicy::EveryN(n) => self.pending.fetch_add(1, AtomicOrdering::Relaxed) + 1 >= n as u64,
+            FlushPolicy::Interval(interval) => Utc::now() - *self.last_flush.read() >= interval,
+        }
+    }
+
+    fn mark_flushed(&self) {
+        self.pending.store(0, AtomicOrdering::Relaxed);
+        *self.last_flush.write() = Utc::now();
+    }
+
+    fn persist_context(&self, context: &RoutingContext) {
+        if let Some(store) = self.store() {
+            if self.due() {
+                store.persist_context(&self.namespace, context);
+                self.mark_flushed();
+            }
+        }
+    }
+
+    fn persist_probabilities(&self, table: &HashMap<Eid, f64>) {
+        if let Some(store) = self.store() {
+            if self.due() {
+                store.persist_probabilities(&self.namespace, table);
+                self.mark_flushed();
+            }
+        }
+    }
+}
+
 pub struct EpidemicRouting {
     name: String,
     contacts: RwLock<Vec<Contact>>,
     routing_history: RwLock<HashMap<Uuid, RoutingContext>>,
+    replications: AtomicU64,
+    persistence: Persistence,
 }
 
 impl EpidemicRouting {
@@ -49,7 +235,21 @@ impl EpidemicRouting {
             name: "epidemic".to_string(),
             contacts: RwLock::new(Vec::new()),
             routing_history: RwLock::new(HashMap::new()),
+            replications: AtomicU64::new(0),
+            persistence: Persistence::none("epidemic"),
+        }
+    }
+
+    /// Load any previously persisted contexts from `store` and write through future mutations
+    /// to it according to `policy`.
+    pub fn with_store(mut self, store: Arc<dyn RoutingStore>, policy: FlushPolicy) -> Self {
+        let mut history = self.routing_history.write();
+        for context in store.load_contexts("epidemic") {
+            history.insert(context.bundle_id, context);
         }
+        drop(history);
+        self.persistence = Persistence::with_store("epidemic", store, policy);
+        self
     }
 
     fn should_replicate(&self, bundle: &Bundle, contact: &Contact) -> bool {
@@ -66,6 +266,11 @@ impl EpidemicRouting {
         let context = history.entry(bundle_id).or_insert_with(|| RoutingContext::new(bundle_id));
         context.encounters.insert(neighbor_eid);
         context.last_updated = Utc::now();
+        let snapshot = context.clone();
+        drop(history);
+
+        self.replications.fetch_add(1, AtomicOrdering::Relaxed);
+        self.persistence.persist_context(&snapshot);
     }
 }
 
@@ -112,9 +317,13 @@ impl RoutingEngine for EpidemicRouting {
         if should_replicate {
             self.update_encounter(bundle.id, contact.neighbor_eid.clone());
         }
-        
+
         should_replicate
     }
+
+    fn metrics(&self) -> Vec<(String, f64)> {
+        vec![("replications_total".to_string(), self.replications.load(AtomicOrdering::Relaxed) as f64)]
+    }
 }
 
 impl Default for EpidemicRouting {
@@ -123,11 +332,27 @@ impl Default for EpidemicRouting {
     }
 }
 
+/// How a [`SprayAndWaitRouting`] relay divides its remaining copies between itself and a newly
+/// met node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SprayMode {
+    /// All copies start at the source; every relay hands over exactly one copy per new
+    /// encounter. Simple, but concentrates copies at the source and is provably slower to
+    /// spread than binary spray.
+    Source,
+    /// A relay holding `n > 1` copies hands over `floor(n/2)` and retains `ceil(n/2)`,
+    /// converging to single-copy "wait" phase in O(log n) hops instead of O(n).
+    Binary,
+}
+
 pub struct SprayAndWaitRouting {
     name: String,
     initial_copies: u32,
+    mode: SprayMode,
     contacts: RwLock<Vec<Contact>>,
     routing_history: RwLock<HashMap<Uuid, RoutingContext>>,
+    copies_sprayed: AtomicU64,
+    persistence: Persistence,
 }
 
 impl SprayAndWaitRouting {
@@ -135,34 +360,85 @@ impl SprayAndWaitRouting {
         Self {
             name: "spray_and_wait".to_string(),
             initial_copies,
+            mode: SprayMode::Source,
             contacts: RwLock::new(Vec::new()),
             routing_history: RwLock::new(HashMap::new()),
+            copies_sprayed: AtomicU64::new(0),
+            persistence: Persistence::none("spray_and_wait"),
         }
     }
 
+    pub fn with_mode(mut self, mode: SprayMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Load any previously persisted contexts from `store` and write through future mutations
+    /// to it according to `policy`.
+    pub fn with_store(mut self, store: Arc<dyn RoutingStore>, policy: FlushPolicy) -> Self {
+        let mut history = self.routing_history.write();
+        for context in store.load_contexts("spray_and_wait") {
+            history.insert(context.bundle_id, context);
+        }
+        drop(history);
+        self.persistence = Persistence::with_store("spray_and_wait", store, policy);
+        self
+    }
+
     fn initialize_context(&self, bundle_id: Uuid) -> RoutingContext {
         let mut context = RoutingContext::new(bundle_id);
         context.copies_left = self.initial_copies;
         context
     }
 
-    fn should_spray(&self, bundle: &Bundle, contact: &Contact) -> bool {
+    /// Initialize (or overwrite) this node's routing context for `bundle_id` with a copy count
+    /// handed over by a spraying neighbor, so a newly-receiving node's count reflects what it
+    /// was actually given rather than assuming `initial_copies`. It is for the forwarding layer
+    /// to call on delivery, with the count the sender's [`Self::should_spray`] returned; no code
+    /// in this crate calls it.
+    pub fn set_received_copies(&self, bundle_id: Uuid, copies: u32) {
+        let mut history = self.routing_history.write();
+        let context = history.entry(bundle_id).or_insert_with(|| RoutingContext::new(bundle_id));
+        context.copies_left = copies;
+        context.last_updated = Utc::now();
+        let snapshot = context.clone();
+        drop(history);
+
+        self.persistence.persist_context(&snapshot);
+    }
+
+    /// Attempts to spray `bundle` to `contact`, mutating this node's held copy count on success
+    /// and returning the number of copies to hand over — for the forwarding layer to pass to
+    /// the receiving node's [`Self::set_received_copies`] — or `None` if we shouldn't forward
+    /// right now (already encountered, or in the single-copy "wait" phase, in either mode,
+    /// without a direct destination contact).
+    pub fn should_spray(&self, bundle: &Bundle, contact: &Contact) -> Option<u32> {
         let mut history = self.routing_history.write();
         let context = history.entry(bundle.id)
             .or_insert_with(|| self.initialize_context(bundle.id));
 
         if bundle.dest_eid == contact.neighbor_eid {
-            return true;
+            return Some(context.copies_left.max(1));
         }
 
-        if context.copies_left > 1 && !context.encounters.contains(&contact.neighbor_eid) {
-            context.copies_left -= 1;
-            context.encounters.insert(contact.neighbor_eid.clone());
-            context.last_updated = Utc::now();
-            true
-        } else {
-            false
+        if context.copies_left <= 1 || context.encounters.contains(&contact.neighbor_eid) {
+            return None;
         }
+
+        let handed = match self.mode {
+            SprayMode::Source => 1,
+            SprayMode::Binary => context.copies_left / 2,
+        };
+
+        context.copies_left -= handed;
+        context.encounters.insert(contact.neighbor_eid.clone());
+        context.last_updated = Utc::now();
+        let snapshot = context.clone();
+        drop(history);
+
+        self.copies_sprayed.fetch_add(handed as u64, AtomicOrdering::Relaxed);
+        self.persistence.persist_context(&snapshot);
+        Some(handed)
     }
 }
 
@@ -199,18 +475,33 @@ impl RoutingEngine for SprayAndWaitRouting {
     }
 
     fn should_forward(&self, bundle: &Bundle, contact: &Contact) -> bool {
-        self.should_spray(bundle, contact)
+        self.should_spray(bundle, contact).is_some()
+    }
+
+    fn metrics(&self) -> Vec<(String, f64)> {
+        let copies_remaining: u32 = self.routing_history.read().values().map(|c| c.copies_left).sum();
+        vec![
+            ("copies_sprayed_total".to_string(), self.copies_sprayed.load(AtomicOrdering::Relaxed) as f64),
+            ("copies_remaining_total".to_string(), copies_remaining as f64),
+        ]
     }
 }
 
 pub struct ProphetRouting {
     name: String,
     alpha: f64,
+    /// Transitivity scaling constant (PROPHET's own `beta`), applied when propagating a
+    /// neighbor's delivery predictabilities: `P(A,C) = max(P(A,C), P(A,B) * P(B,C) * beta)`.
     beta: f64,
     gamma: f64,
+    /// Minimum predictability margin a neighbor must have over us before we forward through
+    /// it. Kept separate from `beta` so tuning the transitivity constant doesn't silently
+    /// change forwarding behavior.
+    forward_threshold: f64,
     delivery_probabilities: RwLock<HashMap<Eid, f64>>,
     contacts: RwLock<Vec<Contact>>,
     last_encounter: RwLock<HashMap<Eid, DateTime<Utc>>>,
+    persistence: Persistence,
 }
 
 impl ProphetRouting {
@@ -220,20 +511,41 @@ impl ProphetRouting {
             alpha: 0.75,
             beta: 0.25,
             gamma: 0.98,
+            forward_threshold: 0.25,
             delivery_probabilities: RwLock::new(HashMap::new()),
             contacts: RwLock::new(Vec::new()),
             last_encounter: RwLock::new(HashMap::new()),
+            persistence: Persistence::none("prophet"),
         }
     }
 
+    pub fn with_forward_threshold(mut self, threshold: f64) -> Self {
+        self.forward_threshold = threshold;
+        self
+    }
+
+    /// Load a previously persisted delivery-probability table from `store` and write through
+    /// future updates to it according to `policy`.
+    pub fn with_store(self, store: Arc<dyn RoutingStore>, policy: FlushPolicy) -> Self {
+        let loaded = store.load_probabilities("prophet");
+        *self.delivery_probabilities.write() = loaded;
+        let persistence = Persistence::with_store("prophet", store, policy);
+        Self { persistence, ..self }
+    }
+
     fn update_delivery_probability(&self, neighbor_eid: &Eid) {
         let mut probabilities = self.delivery_probabilities.write();
         let current_prob = probabilities.get(neighbor_eid).cloned().unwrap_or(0.0);
         let new_prob = current_prob + (1.0 - current_prob) * self.alpha;
         probabilities.insert(neighbor_eid.clone(), new_prob);
-        
+        let snapshot = probabilities.clone();
+        drop(probabilities);
+
         let mut last_encounter = self.last_encounter.write();
         last_encounter.insert(neighbor_eid.clone(), Utc::now());
+        drop(last_encounter);
+
+        self.persistence.persist_probabilities(&snapshot);
     }
 
     fn age_probabilities(&self) {
@@ -253,6 +565,38 @@ impl ProphetRouting {
         self.age_probabilities();
         self.delivery_probabilities.read().get(dest_eid).cloned().unwrap_or(0.0)
     }
+
+    /// Snapshot this node's delivery predictabilities, to be handed to a neighbor on contact so
+    /// it can run its own transitive update against us.
+    pub fn export_predictabilities(&self) -> HashMap<Eid, f64> {
+        self.age_probabilities();
+        self.delivery_probabilities.read().clone()
+    }
+
+    /// Ingest a neighbor `B`'s delivery predictability table on encountering it. `table` is
+    /// expected to come from `B`'s own `export_predictabilities`, which already ages its values
+    /// before returning them; this ages our own table first so the comparison is apples-to-apples,
+    /// then applies the direct update for `B` itself, then propagates transitively — for every
+    /// destination `C` known to `B`, `P(A,C) = max(P(A,C), P(A,B) * P(B,C) * beta)`.
+    pub fn merge_neighbor_predictabilities(&self, neighbor_eid: &Eid, table: HashMap<Eid, f64>) {
+        self.age_probabilities();
+        self.update_delivery_probability(neighbor_eid);
+
+        let p_ab = self.get_delivery_probability(neighbor_eid);
+        let mut probabilities = self.delivery_probabilities.write();
+        for (dest_eid, p_bc) in table {
+            if &dest_eid == neighbor_eid {
+                continue;
+            }
+            let p_ac = probabilities.get(&dest_eid).cloned().unwrap_or(0.0);
+            let transitive = p_ab * p_bc * self.beta;
+            probabilities.insert(dest_eid, p_ac.max(transitive));
+        }
+        let snapshot = probabilities.clone();
+        drop(probabilities);
+
+        self.persistence.persist_probabilities(&snapshot);
+    }
 }
 
 impl RoutingEngine for ProphetRouting {
@@ -303,8 +647,28 @@ impl RoutingEngine for ProphetRouting {
 
         let my_prob = self.get_delivery_probability(&bundle.dest_eid);
         let neighbor_prob = self.get_delivery_probability(&contact.neighbor_eid);
-        
-        neighbor_prob > my_prob + self.beta
+
+        neighbor_prob > my_prob + self.forward_threshold
+    }
+
+    fn metrics(&self) -> Vec<(String, f64)> {
+        self.age_probabilities();
+        let probabilities = self.delivery_probabilities.read();
+        let count = probabilities.len();
+        if count == 0 {
+            return vec![("delivery_predictability_count".to_string(), 0.0)];
+        }
+
+        let sum: f64 = probabilities.values().sum();
+        let min = probabilities.values().cloned().fold(f64::MAX, f64::min);
+        let max = probabilities.values().cloned().fold(f64::MIN, f64::max);
+
+        vec![
+            ("delivery_predictability_count".to_string(), count as f64),
+            ("delivery_predictability_avg".to_string(), sum / count as f64),
+            ("delivery_predictability_min".to_string(), min),
+            ("delivery_predictability_max".to_string(), max),
+        ]
     }
 }
 
@@ -314,6 +678,385 @@ impl Default for ProphetRouting {
     }
 }
 
+/// A single reservation-tracked booking against a scheduled `Contact`, identified by the
+/// contact's neighbor and start time (contacts don't carry a stable id of their own).
+type ContactKey = (Eid, i64);
+
+fn contact_key(contact: &Contact) -> ContactKey {
+    (contact.neighbor_eid.clone(), contact.start_time.timestamp_millis())
+}
+
+/// One frontier state in the Dijkstra search: the node reached, and when.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CgrState {
+    arrival: DateTime<Utc>,
+    node: Eid,
+    confidence: f32,
+    deadline: DateTime<Utc>,
+    path: Vec<Contact>,
+}
+
+impl Ord for CgrState {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reverse so `BinaryHeap` (a max-heap) pops the earliest arrival first.
+        other.arrival.cmp(&self.arrival)
+    }
+}
+
+impl PartialOrd for CgrState {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// A scheduled contact plan: the `Contact`s and `Range`s (one-way light times) known for a
+/// time-varying contact graph, searchable on demand for the best route to a destination. This is
+/// the Dijkstra search engine [`ContactGraphRouting`] is built on; it's factored out on its own
+/// so a caller that just wants "what's the best route right now" (e.g. deciding whether to
+/// accept custody of a bundle) can use [`Self::find_route`] directly instead of going through the
+/// [`RoutingEngine`] trait's contacts-snapshot-then-`compute_routes` dance.
+pub struct ContactPlan {
+    contacts: RwLock<Vec<Contact>>,
+    ranges: RwLock<Vec<Range>>,
+    reserved_bits: RwLock<HashMap<ContactKey, u64>>,
+}
+
+impl ContactPlan {
+    pub fn new(contacts: Vec<Contact>, ranges: Vec<Range>) -> Self {
+        Self {
+            contacts: RwLock::new(contacts),
+            ranges: RwLock::new(ranges),
+            reserved_bits: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Replace the contact plan at runtime, e.g. after receiving an updated schedule.
+    pub fn update_plan(&self, contacts: Vec<Contact>, ranges: Vec<Range>) {
+        *self.contacts.write() = contacts;
+        *self.ranges.write() = ranges;
+        self.reserved_bits.write().clear();
+    }
+
+    pub fn update_contact(&self, contact: Contact) {
+        let mut contacts = self.contacts.write();
+        if let Some(existing) = contacts
+            .iter_mut()
+            .find(|c| c.neighbor_eid == contact.neighbor_eid && c.from_eid == contact.from_eid)
+        {
+            *existing = contact;
+        } else {
+            contacts.push(contact);
+        }
+    }
+
+    pub fn contact_count(&self) -> usize {
+        self.contacts.read().len()
+    }
+
+    pub fn reserved_count(&self) -> usize {
+        self.reserved_bits.read().len()
+    }
+
+    fn owlt_for(&self, from: &Eid, to: &Eid, at: DateTime<Utc>) -> std::time::Duration {
+        self.ranges
+            .read()
+            .iter()
+            .find(|r| {
+                (&r.neighbor_eid == to || &r.neighbor_eid == from)
+                    && at >= r.start_time
+                    && at <= r.end_time
+            })
+            .map(|r| r.owlt)
+            .unwrap_or_default()
+    }
+
+    fn residual_bits(&self, contact: &Contact, arrival_floor: DateTime<Utc>) -> u64 {
+        let remaining = (contact.end_time - arrival_floor).num_milliseconds().max(0) as u64;
+        let capacity = (contact.data_rate as u64).saturating_mul(remaining) / 1000;
+        let reserved = self.reserved_bits.read().get(&contact_key(contact)).copied().unwrap_or(0);
+        capacity.saturating_sub(reserved)
+    }
+
+    /// Dijkstra search over the contact graph from `source` to `dest_eid`, for a bundle of
+    /// `payload_bits` bits starting no earlier than `start`. Each edge is a contact; its cost is
+    /// the earliest feasible arrival time given the contact's window, the one-way light time from
+    /// the matching `Range`, and the transmission time for `payload_bits` at the contact's
+    /// `data_rate` — contacts whose residual capacity before `end_time` can't hold the bundle are
+    /// rejected. `excluded` rules out contacts to those neighbors as the first hop entirely, so a
+    /// caller can retry a failed first hop and have the search find an alternate path instead of
+    /// proposing the same one again. Returns the path of contacts minimizing projected arrival.
+    fn search(
+        &self,
+        source: &Eid,
+        dest_eid: &Eid,
+        payload_bits: u64,
+        start: DateTime<Utc>,
+        excluded: &HashSet<Eid>,
+    ) -> Option<Vec<Contact>> {
+        let contacts = self.contacts.read();
+        let mut heap = BinaryHeap::new();
+        let mut best_arrival: HashMap<Eid, DateTime<Utc>> = HashMap::new();
+
+        heap.push(CgrState {
+            arrival: start,
+            node: source.clone(),
+            confidence: 1.0,
+            deadline: start + chrono::Duration::weeks(52),
+            path: Vec::new(),
+        });
+
+        while let Some(state) = heap.pop() {
+            if &state.node == dest_eid {
+                return Some(state.path);
+            }
+
+            if let Some(&seen) = best_arrival.get(&state.node) {
+                if seen <= state.arrival {
+                    continue;
+                }
+            }
+            best_arrival.insert(state.node.clone(), state.arrival);
+
+            for contact in contacts.iter() {
+                let edge_from = contact.from_eid.clone().unwrap_or_else(|| source.clone());
+                if edge_from != state.node {
+                    continue;
+                }
+
+                if state.path.is_empty() && excluded.contains(&contact.neighbor_eid) {
+                    continue;
+                }
+
+                let depart = state.arrival.max(contact.start_time);
+                if depart > contact.end_time {
+                    continue;
+                }
+
+                if self.residual_bits(contact, depart) < payload_bits {
+                    continue;
+                }
+
+                let transmission_ms = if contact.data_rate == 0 {
+                    continue;
+                } else {
+                    (payload_bits * 1000) / contact.data_rate as u64
+                };
+                let owlt = self.owlt_for(&state.node, &contact.neighbor_eid, depart);
+                let arrival = depart
+                    + chrono::Duration::milliseconds(transmission_ms as i64)
+                    + chrono::Duration::from_std(owlt).unwrap_or_default();
+
+                if arrival > contact.end_time {
+                    continue;
+                }
+
+                let mut path = state.path.clone();
+                path.push(contact.clone());
+
+                heap.push(CgrState {
+                    arrival,
+                    node: contact.neighbor_eid.clone(),
+                    confidence: state.confidence * contact.confidence,
+                    deadline: state.deadline.min(contact.end_time),
+                    path,
+                });
+            }
+        }
+
+        None
+    }
+
+    fn reserve(&self, path: &[Contact], payload_bits: u64) {
+        let mut reserved = self.reserved_bits.write();
+        for contact in path {
+            *reserved.entry(contact_key(contact)).or_insert(0) += payload_bits;
+        }
+    }
+
+    fn route_from_path(dest_eid: &Eid, path: Vec<Contact>) -> Route {
+        let confidence = path.iter().map(|c| c.confidence).product();
+        let valid_until = path.iter().map(|c| c.end_time).min().unwrap();
+        let next_hop = path[0].neighbor_eid.clone();
+
+        Route::new(dest_eid.clone(), next_hop, path.len() as u32)
+            .with_confidence(confidence)
+            .with_validity(valid_until)
+    }
+
+    /// Find the best route to `dest_eid` for a bundle of `bundle_size` bytes, searching from
+    /// `local_eid` no earlier than `now`. Reserves the winning path's capacity so a subsequent
+    /// call doesn't double-book the same bits.
+    pub fn find_route(&self, local_eid: &Eid, dest_eid: &Eid, bundle_size: usize, now: DateTime<Utc>) -> Option<Route> {
+        self.find_route_excluding(local_eid, dest_eid, bundle_size, now, &[])
+    }
+
+    /// Like [`Self::find_route`], but rules out `excluded_hops` as the first hop. Intended for
+    /// retrying after a forward through `excluded_hops` has failed, so the caller gets an
+    /// alternate route instead of the same one again.
+    pub fn find_route_excluding(
+        &self,
+        local_eid: &Eid,
+        dest_eid: &Eid,
+        bundle_size: usize,
+        now: DateTime<Utc>,
+        excluded_hops: &[Eid],
+    ) -> Option<Route> {
+        let payload_bits = bundle_size as u64 * 8;
+        let excluded: HashSet<Eid> = excluded_hops.iter().cloned().collect();
+
+        let path = self.search(local_eid, dest_eid, payload_bits, now, &excluded)?;
+        if path.is_empty() {
+            return None;
+        }
+
+        self.reserve(&path, payload_bits);
+        Some(Self::route_from_path(dest_eid, path))
+    }
+
+    fn has_route(&self, local_eid: &Eid, dest_eid: &Eid, payload_bits: u64, now: DateTime<Utc>, excluded: &HashSet<Eid>) -> bool {
+        self.search(local_eid, dest_eid, payload_bits, now, excluded).is_some()
+    }
+
+    /// Like [`Self::find_route`], but returns up to `max_routes` routes ranked by arrival time
+    /// instead of just the best one: after each route is found, its first-hop neighbor is added
+    /// to the exclusion set and the search reruns, so callers get genuinely distinct backup
+    /// paths rather than the same first hop every time. Each returned route's capacity is
+    /// reserved, same as [`Self::find_route`].
+    pub fn find_routes(&self, local_eid: &Eid, dest_eid: &Eid, bundle_size: usize, now: DateTime<Utc>, max_routes: usize) -> Vec<Route> {
+        let payload_bits = bundle_size as u64 * 8;
+        let mut excluded = HashSet::new();
+        let mut routes = Vec::new();
+
+        while routes.len() < max_routes {
+            let Some(path) = self.search(local_eid, dest_eid, payload_bits, now, &excluded) else {
+                break;
+            };
+            if path.is_empty() {
+                break;
+            }
+
+            excluded.insert(path[0].neighbor_eid.clone());
+            self.reserve(&path, payload_bits);
+            routes.push(Self::route_from_path(dest_eid, path));
+        }
+
+        routes
+    }
+}
+
+/// Contact Graph Routing: computes least-delivery-time routes over a [`ContactPlan`] by running
+/// a Dijkstra-style search where nodes are (EID, time) states.
+pub struct ContactGraphRouting {
+    name: String,
+    plan: ContactPlan,
+    /// Sentinel EID standing in for "this node" in the search, since contacts without a
+    /// `from_eid` (i.e. the local node's own contacts) are matched against whatever `source` the
+    /// search is seeded with rather than a real node identity.
+    source: Eid,
+}
+
+impl ContactGraphRouting {
+    pub fn new(contacts: Vec<Contact>, ranges: Vec<Range>) -> Self {
+        Self {
+            name: "contact_graph".to_string(),
+            plan: ContactPlan::new(contacts, ranges),
+            source: Eid::new("ipn:0.0").expect("ipn:0.0 is a valid EID"),
+        }
+    }
+
+    /// Replace the contact plan at runtime, e.g. after receiving an updated schedule.
+    pub fn update_plan(&self, contacts: Vec<Contact>, ranges: Vec<Range>) {
+        self.plan.update_plan(contacts, ranges);
+    }
+
+    /// Like [`RoutingEngine::compute_routes`], but rules out `excluded_hops` as the first-hop
+    /// neighbor. Intended for retrying after a forward through `excluded_hops` has failed, so
+    /// the caller gets an alternate route instead of the same one again.
+    pub fn compute_routes_excluding(&self, dest_eid: &Eid, contacts: &[Contact], excluded_hops: &[Eid]) -> Vec<Route> {
+        if !contacts.is_empty() {
+            self.update_plan(contacts.to_vec(), self.plan.ranges.read().clone());
+        }
+
+        // A CGR search needs a concrete payload size to respect residual volume; in the
+        // absence of a specific bundle, assume a nominal one-kilobyte bundle for planning.
+        match self.plan.find_route_excluding(&self.source, dest_eid, 1024, Utc::now(), excluded_hops) {
+            Some(route) => vec![route],
+            None => Vec::new(),
+        }
+    }
+
+    /// Compute up to `max_routes` distinct routes to `dest_eid`, ranked by projected arrival
+    /// time, by repeatedly suppressing the previously chosen first-hop contact and re-running
+    /// the search. Useful for backup-route selection when the primary next hop later fails.
+    pub fn compute_backup_routes(&self, dest_eid: &Eid, contacts: &[Contact], max_routes: usize) -> Vec<Route> {
+        if !contacts.is_empty() {
+            self.update_plan(contacts.to_vec(), self.plan.ranges.read().clone());
+        }
+
+        // Same nominal payload-size assumption as `compute_routes`, in the absence of a
+        // specific bundle to size the search against.
+        self.plan.find_routes(&self.source, dest_eid, 1024, Utc::now(), max_routes)
+    }
+
+    /// Like [`RoutingEngine::should_forward`], but treats `contact` as unusable when its
+    /// neighbor appears in `excluded_hops`, and rules those neighbors out as the first hop
+    /// onward from it, so a caller that already tried those hops can check whether a
+    /// genuinely different path still exists.
+    pub fn should_forward_excluding(&self, bundle: &Bundle, contact: &Contact, excluded_hops: &[Eid]) -> bool {
+        if excluded_hops.contains(&contact.neighbor_eid) {
+            return false;
+        }
+        if bundle.dest_eid == contact.neighbor_eid {
+            return true;
+        }
+
+        let excluded: HashSet<Eid> = excluded_hops.iter().cloned().collect();
+        self.plan.has_route(&contact.neighbor_eid, &bundle.dest_eid, bundle.payload_size() as u64 * 8, Utc::now(), &excluded)
+    }
+}
+
+impl RoutingEngine for ContactGraphRouting {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn compute_routes(&self, dest_eid: &Eid, contacts: &[Contact]) -> Vec<Route> {
+        if !contacts.is_empty() {
+            self.update_plan(contacts.to_vec(), self.plan.ranges.read().clone());
+        }
+
+        // A CGR search needs a concrete payload size to respect residual volume; in the
+        // absence of a specific bundle, assume a nominal one-kilobyte bundle for planning.
+        match self.plan.find_route(&self.source, dest_eid, 1024, Utc::now()) {
+            Some(route) => vec![route],
+            None => Vec::new(),
+        }
+    }
+
+    fn update_contact(&self, contact: Contact) {
+        self.plan.update_contact(contact);
+    }
+
+    fn should_forward(&self, bundle: &Bundle, contact: &Contact) -> bool {
+        if bundle.dest_eid == contact.neighbor_eid {
+            return true;
+        }
+
+        self.plan.has_route(&contact.neighbor_eid, &bundle.dest_eid, bundle.payload_size() as u64 * 8, Utc::now(), &HashSet::new())
+    }
+
+    fn find_route(&self, dest_eid: &Eid, bundle_size: usize, now: DateTime<Utc>) -> Option<Route> {
+        self.plan.find_route(&self.source, dest_eid, bundle_size, now)
+    }
+
+    fn metrics(&self) -> Vec<(String, f64)> {
+        vec![
+            ("contacts_known".to_string(), self.plan.contact_count() as f64),
+            ("contacts_reserved".to_string(), self.plan.reserved_count() as f64),
+        ]
+    }
+}
+
 pub struct RoutingManager {
     engines: RwLock<HashMap<String, Arc<dyn RoutingEngine>>>,
     active_engine: RwLock<Option<String>>,
@@ -329,7 +1072,26 @@ impl RoutingManager {
         manager.register_engine(Arc::new(EpidemicRouting::new()));
         manager.register_engine(Arc::new(SprayAndWaitRouting::new(10)));
         manager.register_engine(Arc::new(ProphetRouting::new()));
-        
+        manager.register_engine(Arc::new(ContactGraphRouting::new(Vec::new(), Vec::new())));
+
+        manager
+    }
+
+    /// Like [`Self::new`], but every stateful engine reloads its persisted routing context (or
+    /// PROPHET probability table) from `store` and writes through future mutations to it per
+    /// `policy`. [`ContactGraphRouting`] has no per-bundle state to persist and is registered
+    /// the same as in [`Self::new`].
+    pub fn new_with_store(store: Arc<dyn RoutingStore>, policy: FlushPolicy) -> Self {
+        let manager = Self {
+            engines: RwLock::new(HashMap::new()),
+            active_engine: RwLock::new(None),
+        };
+
+        manager.register_engine(Arc::new(EpidemicRouting::new().with_store(store.clone(), policy)));
+        manager.register_engine(Arc::new(SprayAndWaitRouting::new(10).with_store(store.clone(), policy)));
+        manager.register_engine(Arc::new(ProphetRouting::new().with_store(store, policy)));
+        manager.register_engine(Arc::new(ContactGraphRouting::new(Vec::new(), Vec::new())));
+
         manager
     }
 
@@ -374,6 +1136,12 @@ impl RoutingManager {
         }
     }
 
+    /// Look up the best route for a bundle of `bundle_size` bytes via the active engine,
+    /// consulting its scheduled [`ContactPlan`] (if any) rather than a static next-hop table.
+    pub fn find_route(&self, dest_eid: &Eid, bundle_size: usize, now: DateTime<Utc>) -> Option<Route> {
+        self.get_active_engine()?.find_route(dest_eid, bundle_size, now)
+    }
+
     pub fn update_contact(&self, contact: Contact) {
         if let Some(engine) = self.get_active_engine() {
             engine.update_contact(contact);
@@ -383,10 +1151,265 @@ impl RoutingManager {
     pub fn list_engines(&self) -> Vec<String> {
         self.engines.read().keys().cloned().collect()
     }
+
+    /// OpenMetrics/Prometheus text exposition of the active engine's [`RoutingEngine::metrics`],
+    /// each line labeled with the active engine's name so dashboards can tell engines apart
+    /// across a fleet of nodes that may each run a different routing strategy.
+    pub fn metrics_text(&self) -> String {
+        let mut out = String::new();
+        if let Some(engine) = self.get_active_engine() {
+            let name = engine.name().to_string();
+
+            for (metric_name, value) in engine.metrics() {
+                let full_name = format!("bp_routing_{}", metric_name);
+                out.push_str(&format!(
+                    "# TYPE {} gauge\n{}{{engine=\"{}\"}} {}\n",
+                    full_name, full_name, name, value
+                ));
+            }
+        }
+
+        out
+    }
 }
 
 impl Default for RoutingManager {
     fn default() -> Self {
         Self::new()
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Bundle;
+
+    fn eid(s: &str) -> Eid {
+        Eid::new(s).unwrap()
+    }
+
+    #[test]
+    fn test_contact_plan_search_finds_no_route_when_disconnected() {
+        let plan = ContactPlan::new(Vec::new(), Vec::new());
+        let now = Utc::now();
+        let path = plan.search(&eid("ipn:1.1"), &eid("ipn:2.1"), 800, now, &HashSet::new());
+        assert!(path.is_none());
+        assert!(plan.find_route(&eid("ipn:1.1"), &eid("ipn:2.1"), 100, now).is_none());
+    }
+
+    #[test]
+    fn test_contact_plan_search_and_reserve_exhausts_capacity() {
+        let now = Utc::now();
+        let dest = eid("ipn:2.1");
+        // 1000 bits/sec for 10 seconds = 10,000 bits of capacity.
+        let contact = Contact::new(dest.clone(), now, now + chrono::Duration::seconds(10), 1000);
+        let plan = ContactPlan::new(vec![contact], Vec::new());
+
+        // First an 8,000-bit bundle books most of the capacity...
+        let route = plan.find_route(&eid("ipn:1.1"), &dest, 1000, now);
+        assert!(route.is_some());
+        assert_eq!(plan.reserved_count(), 1);
+
+        // ...leaving only 2,000 bits, too little for a second 8,000-bit bundle.
+        let second = plan.find_route(&eid("ipn:1.1"), &dest, 1000, now);
+        assert!(second.is_none());
+    }
+
+    #[test]
+    fn test_contact_plan_find_routes_returns_k_best_distinct_first_hops_in_arrival_order() {
+        let now = Utc::now();
+        let source = eid("ipn:1.1");
+        let dest = eid("ipn:9.9");
+        let fast_relay = eid("ipn:2.1");
+        let slow_relay = eid("ipn:3.1");
+
+        let to_fast_relay = Contact::new(fast_relay.clone(), now, now + chrono::Duration::hours(1), 1_000_000);
+        let fast_relay_to_dest = Contact::new(dest.clone(), now, now + chrono::Duration::hours(1), 1_000_000)
+            .with_from_eid(fast_relay.clone());
+
+        let to_slow_relay = Contact::new(
+            slow_relay.clone(),
+            now + chrono::Duration::minutes(30),
+            now + chrono::Duration::hours(2),
+            1_000_000,
+        );
+        let slow_relay_to_dest = Contact::new(dest.clone(), now + chrono::Duration::minutes(30), now + chrono::Duration::hours(2), 1_000_000)
+            .with_from_eid(slow_relay.clone());
+
+        let plan = ContactPlan::new(
+            vec![to_fast_relay, fast_relay_to_dest, to_slow_relay, slow_relay_to_dest],
+            Vec::new(),
+        );
+
+        let routes = plan.find_routes(&source, &dest, 100, now, 2);
+        assert_eq!(routes.len(), 2);
+        assert_eq!(routes[0].next_hop, fast_relay);
+        assert_eq!(routes[1].next_hop, slow_relay);
+    }
+
+    #[test]
+    fn test_prophet_delivery_probability_updates_toward_one_on_encounter() {
+        let prophet = ProphetRouting::new();
+        let neighbor = eid("ipn:2.1");
+
+        prophet.update_contact(Contact::new(neighbor.clone(), Utc::now(), Utc::now() + chrono::Duration::hours(1), 1000));
+        let first = prophet.get_delivery_probability(&neighbor);
+        assert!((first - prophet.alpha).abs() < 1e-9);
+
+        prophet.update_contact(Contact::new(neighbor.clone(), Utc::now(), Utc::now() + chrono::Duration::hours(1), 1000));
+        let second = prophet.get_delivery_probability(&neighbor);
+        let expected = first + (1.0 - first) * prophet.alpha;
+        assert!((second - expected).abs() < 1e-9);
+        assert!(second > first);
+    }
+
+    #[test]
+    fn test_prophet_probabilities_age_toward_zero_without_encounters() {
+        let prophet = ProphetRouting::new();
+        let neighbor = eid("ipn:2.1");
+        prophet.update_delivery_probability(&neighbor);
+        let fresh = prophet.get_delivery_probability(&neighbor);
+
+        // Back-date the encounter so the next read ages it, without waiting in real time.
+        prophet.last_encounter.write().insert(neighbor.clone(), Utc::now() - chrono::Duration::hours(2));
+        let aged = prophet.get_delivery_probability(&neighbor);
+
+        assert!(aged < fresh);
+        let expected = fresh * prophet.gamma.powf(2.0);
+        assert!((aged - expected).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_prophet_merge_applies_transitive_update() {
+        let prophet = ProphetRouting::new();
+        let neighbor = eid("ipn:2.1");
+        let far_dest = eid("ipn:3.1");
+
+        // One encounter before the merge, so `merge_neighbor_predictabilities`'s own direct
+        // update ratchets it a second time: 0.75, then 0.75 + (1 - 0.75) * 0.75 = 0.9375.
+        prophet.update_delivery_probability(&neighbor);
+
+        let mut neighbor_table = HashMap::new();
+        neighbor_table.insert(far_dest.clone(), 0.8);
+        prophet.merge_neighbor_predictabilities(&neighbor, neighbor_table);
+
+        let p_ab_after_merge = prophet.get_delivery_probability(&neighbor);
+        assert!((p_ab_after_merge - 0.9375).abs() < 1e-6);
+
+        let p_ac = prophet.get_delivery_probability(&far_dest);
+        let expected = p_ab_after_merge * 0.8 * prophet.beta;
+        assert!((p_ac - expected).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_spray_and_wait_source_mode_hands_over_exactly_one_copy() {
+        let spray = SprayAndWaitRouting::new(4).with_mode(SprayMode::Source);
+        let bundle = Bundle::new(eid("ipn:1.1"), eid("ipn:9.9"), "payload");
+        let neighbor_a = Contact::new(eid("ipn:2.1"), Utc::now(), Utc::now() + chrono::Duration::hours(1), 1000);
+        let neighbor_b = Contact::new(eid("ipn:3.1"), Utc::now(), Utc::now() + chrono::Duration::hours(1), 1000);
+
+        let handed = spray.should_spray(&bundle, &neighbor_a).unwrap();
+        assert_eq!(handed, 1);
+
+        let handed_again = spray.should_spray(&bundle, &neighbor_b).unwrap();
+        assert_eq!(handed_again, 1);
+    }
+
+    #[test]
+    fn test_spray_and_wait_binary_mode_halves_remaining_copies() {
+        let spray = SprayAndWaitRouting::new(8).with_mode(SprayMode::Binary);
+        let bundle = Bundle::new(eid("ipn:1.1"), eid("ipn:9.9"), "payload");
+        let neighbor_a = Contact::new(eid("ipn:2.1"), Utc::now(), Utc::now() + chrono::Duration::hours(1), 1000);
+        let neighbor_b = Contact::new(eid("ipn:3.1"), Utc::now(), Utc::now() + chrono::Duration::hours(1), 1000);
+
+        // 8 copies -> hand over 4, keep 4.
+        let first = spray.should_spray(&bundle, &neighbor_a).unwrap();
+        assert_eq!(first, 4);
+
+        // 4 copies left -> hand over 2, keep 2.
+        let second = spray.should_spray(&bundle, &neighbor_b).unwrap();
+        assert_eq!(second, 2);
+    }
+
+    #[test]
+    fn test_spray_and_wait_stops_once_down_to_a_single_copy() {
+        let spray = SprayAndWaitRouting::new(2).with_mode(SprayMode::Binary);
+        let bundle = Bundle::new(eid("ipn:1.1"), eid("ipn:9.9"), "payload");
+        let neighbor_a = Contact::new(eid("ipn:2.1"), Utc::now(), Utc::now() + chrono::Duration::hours(1), 1000);
+        let neighbor_b = Contact::new(eid("ipn:3.1"), Utc::now(), Utc::now() + chrono::Duration::hours(1), 1000);
+
+        let first = spray.should_spray(&bundle, &neighbor_a).unwrap();
+        assert_eq!(first, 1);
+
+        // Only a single copy remains; a non-destination encounter must not spray it away.
+        assert!(spray.should_spray(&bundle, &neighbor_b).is_none());
+    }
+
+    #[test]
+    fn test_spray_and_wait_always_delivers_direct_to_destination() {
+        let spray = SprayAndWaitRouting::new(2).with_mode(SprayMode::Binary);
+        let dest = eid("ipn:9.9");
+        let bundle = Bundle::new(eid("ipn:1.1"), dest.clone(), "payload");
+        let direct_contact = Contact::new(dest, Utc::now(), Utc::now() + chrono::Duration::hours(1), 1000);
+
+        assert!(spray.should_spray(&bundle, &direct_contact).is_some());
+    }
+
+    #[test]
+    fn test_persistence_due_immediate_flushes_every_time() {
+        let persistence = Persistence::none("test");
+        assert!(persistence.due());
+        assert!(persistence.due());
+    }
+
+    #[test]
+    fn test_persistence_due_every_n_flushes_only_after_threshold() {
+        let persistence = Persistence {
+            policy: FlushPolicy::EveryN(3),
+            ..Persistence::none("test")
+        };
+
+        assert!(!persistence.due());
+        assert!(!persistence.due());
+        assert!(persistence.due());
+
+        persistence.mark_flushed();
+        assert!(!persistence.due());
+    }
+
+    #[test]
+    fn test_persistence_due_interval_respects_elapsed_time() {
+        let persistence = Persistence {
+            policy: FlushPolicy::Interval(chrono::Duration::minutes(5)),
+            ..Persistence::none("test")
+        };
+        assert!(!persistence.due());
+
+        *persistence.last_flush.write() = Utc::now() - chrono::Duration::minutes(10);
+        assert!(persistence.due());
+    }
+
+    #[test]
+    fn test_file_routing_store_round_trips_contexts_and_probabilities() {
+        let root = std::env::temp_dir().join(format!("bp-sdk-routing-store-test-{:?}", std::thread::current().id()));
+        let store = FileRoutingStore::new(&root);
+
+        let mut context = RoutingContext::new(Uuid::new_v4());
+        context.copies_left = 3;
+        store.persist_context("spray_and_wait", &context);
+
+        let loaded = store.load_contexts("spray_and_wait");
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].bundle_id, context.bundle_id);
+        assert_eq!(loaded[0].copies_left, 3);
+
+        let mut table = HashMap::new();
+        table.insert(eid("ipn:2.1"), 0.42);
+        store.persist_probabilities("prophet", &table);
+
+        let loaded_table = store.load_probabilities("prophet");
+        assert_eq!(loaded_table.get(&eid("ipn:2.1")), Some(&0.42));
+
+        std::fs::remove_dir_all(&root).ok();
+    }
 } 
\ No newline at end of file
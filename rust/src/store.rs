@@ -0,0 +1,191 @@
+//! Store-and-forward persistence for bundles that can't be delivered immediately.
+//!
+//! [`ClaManager::send_bundle`](crate::cla::ClaManager::send_bundle) enqueues into a
+//! [`BundleStore`] instead of failing outright when no contact is available, and
+//! [`ClaManager::retry_pending`](crate::cla::ClaManager::retry_pending) — driven by a
+//! periodic background task — retries delivery with exponential backoff until the bundle's
+//! lifetime expires.
+
+use crate::types::Bundle;
+use chrono::{DateTime, Utc};
+use parking_lot::RwLock;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use uuid::Uuid;
+
+/// Key a [`StoredBundle`] is held under; the bundle's own id.
+pub type BundleId = Uuid;
+
+/// Initial retry backoff.
+const INITIAL_BACKOFF_SECS: i64 = 1;
+/// Backoff growth factor per failed attempt.
+const BACKOFF_FACTOR: i64 = 2;
+/// Backoff ceiling, so a long partition doesn't stretch retries out indefinitely.
+const MAX_BACKOFF_SECS: i64 = 300;
+
+/// A bundle held for later delivery: its payload, where it was headed, and when it expires.
+#[derive(Debug, Clone)]
+pub struct StoredBundle {
+    pub bundle: Bundle,
+    pub protocol: String,
+    pub dest_addr: String,
+    pub expires_at: DateTime<Utc>,
+    pub attempts: u32,
+    pub next_retry: DateTime<Utc>,
+}
+
+impl StoredBundle {
+    fn new(bundle: Bundle, protocol: String, dest_addr: String) -> Self {
+        let expires_at = bundle.creation_time.to_datetime()
+            + chrono::Duration::from_std(bundle.ttl).unwrap_or_else(|_| chrono::Duration::zero());
+        Self {
+            bundle,
+            protocol,
+            dest_addr,
+            expires_at,
+            attempts: 0,
+            next_retry: Utc::now(),
+        }
+    }
+
+    pub fn is_expired(&self) -> bool {
+        Utc::now() >= self.expires_at
+    }
+}
+
+/// Holds outbound bundles that couldn't be delivered yet.
+pub trait BundleStore: Send + Sync + Debug {
+    /// Persist a bundle for later (re)delivery over `protocol` to `dest_addr`.
+    fn enqueue(&self, bundle: Bundle, protocol: String, dest_addr: String);
+
+    /// Entries whose retry backoff has elapsed and that have not yet expired.
+    fn due_for_retry(&self) -> Vec<StoredBundle>;
+
+    /// Record the outcome of a delivery attempt: drop the entry on success, otherwise bump
+    /// its attempt count and back off the next retry.
+    fn record_attempt(&self, id: BundleId, success: bool);
+
+    /// Drop and return the ids of every entry past its `expires_at`.
+    fn sweep_expired(&self) -> Vec<BundleId>;
+
+    /// Number of bundles currently held.
+    fn len(&self) -> usize;
+}
+
+/// Default in-memory [`BundleStore`].
+#[derive(Debug, Default)]
+pub struct InMemoryBundleStore {
+    entries: RwLock<HashMap<BundleId, StoredBundle>>,
+}
+
+impl InMemoryBundleStore {
+    pub fn new() -> Self {
+        Self { entries: RwLock::new(HashMap::new()) }
+    }
+}
+
+impl BundleStore for InMemoryBundleStore {
+    fn enqueue(&self, bundle: Bundle, protocol: String, dest_addr: String) {
+        let id = bundle.id;
+        self.entries.write().insert(id, StoredBundle::new(bundle, protocol, dest_addr));
+    }
+
+    fn due_for_retry(&self) -> Vec<StoredBundle> {
+        let now = Utc::now();
+        self.entries
+            .read()
+            .values()
+            .filter(|entry| !entry.is_expired() && entry.next_retry <= now)
+            .cloned()
+            .collect()
+    }
+
+    fn record_attempt(&self, id: BundleId, success: bool) {
+        let mut entries = self.entries.write();
+        if success {
+            entries.remove(&id);
+            return;
+        }
+        if let Some(entry) = entries.get_mut(&id) {
+            entry.attempts += 1;
+            let backoff = INITIAL_BACKOFF_SECS
+                .saturating_mul(BACKOFF_FACTOR.saturating_pow(entry.attempts.saturating_sub(1)))
+                .min(MAX_BACKOFF_SECS);
+            entry.next_retry = Utc::now() + chrono::Duration::seconds(backoff);
+        }
+    }
+
+    fn sweep_expired(&self) -> Vec<BundleId> {
+        let mut entries = self.entries.write();
+        let expired: Vec<BundleId> = entries.values().filter(|entry| entry.is_expired()).map(|entry| entry.bundle.id).collect();
+        for id in &expired {
+            entries.remove(id);
+        }
+        expired
+    }
+
+    fn len(&self) -> usize {
+        self.entries.read().len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Eid, Priority};
+    use std::time::Duration;
+
+    fn sample_bundle(ttl: Duration) -> Bundle {
+        Bundle::new(Eid::new("ipn:1.1").unwrap(), Eid::new("ipn:2.1").unwrap(), "store test")
+            .with_priority(Priority::Normal)
+            .with_ttl(ttl)
+    }
+
+    #[test]
+    fn test_enqueue_and_retry_immediately_due() {
+        let store = InMemoryBundleStore::new();
+        let bundle = sample_bundle(Duration::from_secs(3600));
+        let id = bundle.id;
+        store.enqueue(bundle, "tcp".to_string(), "127.0.0.1:4556".to_string());
+
+        assert_eq!(store.len(), 1);
+        let due = store.due_for_retry();
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].bundle.id, id);
+    }
+
+    #[test]
+    fn test_failed_attempt_backs_off_next_retry() {
+        let store = InMemoryBundleStore::new();
+        let bundle = sample_bundle(Duration::from_secs(3600));
+        let id = bundle.id;
+        store.enqueue(bundle, "tcp".to_string(), "127.0.0.1:4556".to_string());
+
+        store.record_attempt(id, false);
+        assert!(store.due_for_retry().is_empty());
+    }
+
+    #[test]
+    fn test_successful_attempt_removes_entry() {
+        let store = InMemoryBundleStore::new();
+        let bundle = sample_bundle(Duration::from_secs(3600));
+        let id = bundle.id;
+        store.enqueue(bundle, "tcp".to_string(), "127.0.0.1:4556".to_string());
+
+        store.record_attempt(id, true);
+        assert_eq!(store.len(), 0);
+    }
+
+    #[test]
+    fn test_sweep_evicts_expired_entries() {
+        let store = InMemoryBundleStore::new();
+        let bundle = sample_bundle(Duration::from_millis(1));
+        let id = bundle.id;
+        store.enqueue(bundle, "tcp".to_string(), "127.0.0.1:4556".to_string());
+
+        std::thread::sleep(Duration::from_millis(10));
+        let expired = store.sweep_expired();
+        assert_eq!(expired, vec![id]);
+        assert_eq!(store.len(), 0);
+    }
+}
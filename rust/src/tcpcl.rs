@@ -0,0 +1,517 @@
+//! TCPCLv4-style contact negotiation and message segmentation for [`crate::cla::TcpCla`].
+//!
+//! Real Bundle Protocol peers expect a contact header handshake before any bundle data,
+//! and expect transfers to arrive as acknowledged, possibly-segmented XFER messages rather
+//! than a raw write. This module implements that session layer independently of the
+//! `Cla` trait so it can be unit tested without a live socket. The XFER_ACK for each
+//! segment is written by the sending side right after the segment; the receiving side only
+//! checks it.
+
+use crate::error::{BpError, BpResult};
+use bytes::{Bytes, BytesMut};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+/// Magic bytes that open every TCPCL contact header.
+pub const CONTACT_MAGIC: &[u8; 4] = b"dtn!";
+
+/// Contact-layer version this SDK speaks.
+pub const CONTACT_VERSION: u8 = 4;
+
+/// Segment carries the first chunk of a transfer.
+pub const SEG_FLAG_START: u8 = 0x1;
+/// Segment carries the last chunk of a transfer.
+pub const SEG_FLAG_END: u8 = 0x2;
+
+const MSG_XFER_SEGMENT: u8 = 1;
+const MSG_XFER_ACK: u8 = 2;
+const MSG_KEEPALIVE: u8 = 3;
+const MSG_SESS_TERM: u8 = 4;
+
+/// Where a [`TcpClSession`] is in the TCPCL contact/session lifecycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionState {
+    Idle,
+    ContactSent,
+    SessionEstablished,
+    Closing,
+}
+
+/// The contact header exchanged before any bundle transfer: magic, version, flags, the
+/// sender's proposed keepalive interval and idle/peer timeout, and its EID. Both numeric
+/// values are proposals — [`TcpClSession::recv_contact_header`] negotiates the minimum of
+/// each against the peer's advertised value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContactHeader {
+    pub version: u8,
+    pub flags: u8,
+    pub keepalive_interval: u16,
+    pub peer_timeout: u16,
+    pub eid: String,
+}
+
+impl ContactHeader {
+    pub fn new(eid: impl Into<String>, keepalive_interval: u16, peer_timeout: u16) -> Self {
+        Self { version: CONTACT_VERSION, flags: 0, keepalive_interval, peer_timeout, eid: eid.into() }
+    }
+
+    pub fn encode(&self) -> Bytes {
+        let eid_bytes = self.eid.as_bytes();
+        let mut out = BytesMut::with_capacity(12 + eid_bytes.len());
+        out.extend_from_slice(CONTACT_MAGIC);
+        out.extend_from_slice(&[self.version, self.flags]);
+        out.extend_from_slice(&self.keepalive_interval.to_be_bytes());
+        out.extend_from_slice(&self.peer_timeout.to_be_bytes());
+        out.extend_from_slice(&(eid_bytes.len() as u16).to_be_bytes());
+        out.extend_from_slice(eid_bytes);
+        out.freeze()
+    }
+
+    pub fn decode(data: &[u8]) -> BpResult<Self> {
+        if data.len() < 12 || &data[0..4] != CONTACT_MAGIC {
+            return Err(BpError::Protocol("invalid TCPCL contact header".to_string()));
+        }
+        let version = data[4];
+        let flags = data[5];
+        let keepalive_interval = u16::from_be_bytes([data[6], data[7]]);
+        let peer_timeout = u16::from_be_bytes([data[8], data[9]]);
+        let eid_len = u16::from_be_bytes([data[10], data[11]]) as usize;
+        let eid_start = 12;
+        if data.len() < eid_start + eid_len {
+            return Err(BpError::Protocol("truncated TCPCL contact header".to_string()));
+        }
+        let eid = String::from_utf8(data[eid_start..eid_start + eid_len].to_vec())
+            .map_err(|_| BpError::Protocol("non-UTF8 EID in contact header".to_string()))?;
+        Ok(Self { version, flags, keepalive_interval, peer_timeout, eid })
+    }
+}
+
+/// A reassembled bundle transfer, or a control message, read off a TCPCL session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TcpClMessage {
+    Bundle(Bytes),
+    Keepalive,
+    SessTerm,
+}
+
+/// Emitted when a [`TcpClSession`] comes up or goes down, so a caller driving many
+/// concurrent sessions (e.g. [`crate::cla::TcpCla`]) can react without polling `state`.
+/// Mirrors [`crate::peers::PeerEvent`], but keyed by EID and carrying the negotiated
+/// session parameters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SessionEvent {
+    Up {
+        peer_eid: String,
+        keepalive_interval: u16,
+        peer_timeout: u16,
+        /// The peer's leaf TLS certificate (DER-encoded), if this session runs over a
+        /// TLS-wrapped stream and the peer presented one. Always `None` for a plain TCP
+        /// session; [`TcpClSession`] itself is transport-agnostic, so this is filled in by
+        /// the caller (e.g. [`crate::cla::TcpCla`]) after the handshake completes.
+        peer_certificate: Option<Bytes>,
+    },
+    Down { peer_eid: Option<String> },
+}
+
+/// Negotiated TCPCL session state for one peer connection: contact handshake result plus
+/// in-progress segment reassembly.
+#[derive(Debug)]
+pub struct TcpClSession {
+    pub state: SessionState,
+    pub local_eid: String,
+    pub peer_eid: Option<String>,
+    /// Outbound chunk size for [`Self::send_bundle`], and the cap [`Self::recv_message`]
+    /// enforces against each inbound XFER segment's claimed length, rejecting a peer that
+    /// claims more with `BpError::Protocol` rather than allocating an unbounded buffer.
+    pub max_segment_size: usize,
+    /// Keepalive interval (seconds); the value this side proposes until the handshake
+    /// completes, after which it holds `min(local, peer)`.
+    pub keepalive_interval: u16,
+    /// Idle timeout (seconds); negotiated the same way as `keepalive_interval`.
+    pub peer_timeout: u16,
+    next_transfer_id: u64,
+    partial: Option<(u64, BytesMut)>,
+}
+
+impl TcpClSession {
+    pub fn new(local_eid: impl Into<String>, keepalive_interval: u16, max_segment_size: usize) -> Self {
+        Self::with_peer_timeout(local_eid, keepalive_interval, 3 * keepalive_interval, max_segment_size)
+    }
+
+    /// Like [`Self::new`], but also proposing `peer_timeout` (seconds) as this side's
+    /// idle-teardown threshold instead of the default of three keepalive intervals.
+    pub fn with_peer_timeout(
+        local_eid: impl Into<String>,
+        keepalive_interval: u16,
+        peer_timeout: u16,
+        max_segment_size: usize,
+    ) -> Self {
+        Self {
+            state: SessionState::Idle,
+            local_eid: local_eid.into(),
+            peer_eid: None,
+            max_segment_size,
+            keepalive_interval,
+            peer_timeout,
+            next_transfer_id: 0,
+            partial: None,
+        }
+    }
+
+    /// Active-side handshake: send our contact header, then read the peer's, returning the
+    /// [`SessionEvent::Up`] once negotiation completes.
+    pub async fn connect_handshake<S: AsyncReadExt + AsyncWriteExt + Unpin>(
+        &mut self,
+        stream: &mut S,
+    ) -> BpResult<SessionEvent> {
+        self.send_contact_header(stream).await?;
+        self.recv_contact_header(stream).await
+    }
+
+    /// Passive-side handshake: read the peer's contact header, then send ours, returning
+    /// the [`SessionEvent::Up`] once negotiation completes.
+    pub async fn accept_handshake<S: AsyncReadExt + AsyncWriteExt + Unpin>(
+        &mut self,
+        stream: &mut S,
+    ) -> BpResult<SessionEvent> {
+        let event = self.recv_contact_header(stream).await?;
+        self.send_contact_header(stream).await?;
+        self.state = SessionState::SessionEstablished;
+        Ok(event)
+    }
+
+    async fn send_contact_header<S: AsyncWriteExt + Unpin>(&mut self, stream: &mut S) -> BpResult<()> {
+        let header = ContactHeader::new(self.local_eid.clone(), self.keepalive_interval, self.peer_timeout);
+        stream
+            .write_all(&header.encode())
+            .await
+            .map_err(|e| BpError::Protocol(format!("failed to send contact header: {}", e)))?;
+        self.state = SessionState::ContactSent;
+        Ok(())
+    }
+
+    /// Read the peer's contact header and negotiate: both `keepalive_interval` and
+    /// `peer_timeout` are replaced with the minimum of this side's proposal and the peer's.
+    async fn recv_contact_header<S: AsyncReadExt + Unpin>(&mut self, stream: &mut S) -> BpResult<SessionEvent> {
+        let mut prefix = [0u8; 12];
+        stream
+            .read_exact(&mut prefix)
+            .await
+            .map_err(|e| BpError::Protocol(format!("failed to read contact header: {}", e)))?;
+        let eid_len = u16::from_be_bytes([prefix[10], prefix[11]]) as usize;
+
+        let mut buf = vec![0u8; 12 + eid_len];
+        buf[..12].copy_from_slice(&prefix);
+        stream
+            .read_exact(&mut buf[12..])
+            .await
+            .map_err(|e| BpError::Protocol(format!("failed to read contact header EID: {}", e)))?;
+
+        let header = ContactHeader::decode(&buf)?;
+        self.peer_eid = Some(header.eid);
+        self.keepalive_interval = self.keepalive_interval.min(header.keepalive_interval);
+        self.peer_timeout = self.peer_timeout.min(header.peer_timeout);
+        self.state = SessionState::SessionEstablished;
+        Ok(SessionEvent::Up {
+            peer_eid: self.peer_eid.clone().unwrap(),
+            keepalive_interval: self.keepalive_interval,
+            peer_timeout: self.peer_timeout,
+            peer_certificate: None,
+        })
+    }
+
+    /// Split `data` into max-segment-size chunks and write them as XFER_SEGMENT messages.
+    pub async fn send_bundle<S: AsyncWriteExt + Unpin>(&mut self, stream: &mut S, data: &[u8]) -> BpResult<()> {
+        if self.state != SessionState::SessionEstablished {
+            return Err(BpError::Protocol("cannot transfer before session is established".to_string()));
+        }
+
+        let transfer_id = self.next_transfer_id;
+        self.next_transfer_id += 1;
+
+        let chunks: Vec<&[u8]> = if data.is_empty() {
+            vec![&data[..]]
+        } else {
+            data.chunks(self.max_segment_size.max(1)).collect()
+        };
+        let last = chunks.len() - 1;
+
+        for (i, chunk) in chunks.into_iter().enumerate() {
+            let mut flags = 0u8;
+            if i == 0 {
+                flags |= SEG_FLAG_START;
+            }
+            if i == last {
+                flags |= SEG_FLAG_END;
+            }
+            self.write_segment(stream, transfer_id, flags, chunk).await?;
+        }
+        Ok(())
+    }
+
+    async fn write_segment<S: AsyncWriteExt + Unpin>(
+        &self,
+        stream: &mut S,
+        transfer_id: u64,
+        flags: u8,
+        chunk: &[u8],
+    ) -> BpResult<()> {
+        let mut out = BytesMut::with_capacity(14 + chunk.len());
+        out.extend_from_slice(&[MSG_XFER_SEGMENT]);
+        out.extend_from_slice(&transfer_id.to_be_bytes());
+        out.extend_from_slice(&[flags]);
+        out.extend_from_slice(&(chunk.len() as u32).to_be_bytes());
+        out.extend_from_slice(chunk);
+
+        stream
+            .write_all(&out)
+            .await
+            .map_err(|e| BpError::Protocol(format!("failed to write XFER segment: {}", e)))?;
+
+        let mut ack = BytesMut::with_capacity(9);
+        ack.extend_from_slice(&[MSG_XFER_ACK]);
+        ack.extend_from_slice(&transfer_id.to_be_bytes());
+        stream
+            .write_all(&ack)
+            .await
+            .map_err(|e| BpError::Protocol(format!("failed to write XFER ack: {}", e)))?;
+        Ok(())
+    }
+
+    /// Send a KEEPALIVE control message, driven by the negotiated `keepalive_interval`.
+    pub async fn send_keepalive<S: AsyncWriteExt + Unpin>(&self, stream: &mut S) -> BpResult<()> {
+        stream
+            .write_all(&[MSG_KEEPALIVE])
+            .await
+            .map_err(|e| BpError::Protocol(format!("failed to send keepalive: {}", e)))
+    }
+
+    /// Send SESS_TERM and move this session into `Closing`.
+    pub async fn close<S: AsyncWriteExt + Unpin>(&mut self, stream: &mut S) -> BpResult<()> {
+        stream
+            .write_all(&[MSG_SESS_TERM])
+            .await
+            .map_err(|e| BpError::Protocol(format!("failed to send session terminate: {}", e)))?;
+        self.state = SessionState::Closing;
+        Ok(())
+    }
+
+    /// Read one message off the session, reassembling XFER_SEGMENT chains into a complete
+    /// bundle transfer. Each segment must be followed by the XFER_ACK that
+    /// [`Self::send_bundle`] writes right after it on the same stream; this reads and checks
+    /// that ACK and writes no acknowledgement back to the peer.
+    pub async fn recv_message<S: AsyncReadExt + Unpin>(&mut self, stream: &mut S) -> BpResult<TcpClMessage> {
+        loop {
+            let mut kind = [0u8; 1];
+            stream
+                .read_exact(&mut kind)
+                .await
+                .map_err(|e| BpError::Protocol(format!("failed to read message kind: {}", e)))?;
+
+            match kind[0] {
+                MSG_XFER_SEGMENT => {
+                    let mut header = [0u8; 13];
+                    stream
+                        .read_exact(&mut header)
+                        .await
+                        .map_err(|e| BpError::Protocol(format!("failed to read XFER header: {}", e)))?;
+                    let transfer_id = u64::from_be_bytes(header[0..8].try_into().unwrap());
+                    let flags = header[8];
+                    let len = u32::from_be_bytes(header[9..13].try_into().unwrap()) as usize;
+
+                    if len > self.max_segment_size {
+                        return Err(BpError::Protocol("XFER segment exceeds max payload size".to_string()));
+                    }
+
+                    let mut chunk = vec![0u8; len];
+                    stream
+                        .read_exact(&mut chunk)
+                        .await
+                        .map_err(|e| BpError::Protocol(format!("failed to read XFER payload: {}", e)))?;
+
+                    let mut ack = [0u8; 9];
+                    stream
+                        .read_exact(&mut ack)
+                        .await
+                        .map_err(|e| BpError::Protocol(format!("failed to read XFER ack: {}", e)))?;
+                    if ack[0] != MSG_XFER_ACK {
+                        return Err(BpError::Protocol("expected XFER_ACK after segment".to_string()));
+                    }
+
+                    if flags & SEG_FLAG_START != 0 {
+                        self.partial = Some((transfer_id, BytesMut::new()));
+                    }
+                    match &mut self.partial {
+                        Some((id, buf)) if *id == transfer_id => buf.extend_from_slice(&chunk),
+                        _ => return Err(BpError::Protocol("XFER segment for unknown transfer".to_string())),
+                    }
+
+                    if flags & SEG_FLAG_END != 0 {
+                        let (_, buf) = self.partial.take().unwrap();
+                        return Ok(TcpClMessage::Bundle(buf.freeze()));
+                    }
+                }
+                MSG_KEEPALIVE => return Ok(TcpClMessage::Keepalive),
+                MSG_SESS_TERM => {
+                    self.state = SessionState::Closing;
+                    return Ok(TcpClMessage::SessTerm);
+                }
+                other => return Err(BpError::Protocol(format!("unknown TCPCL message kind {}", other))),
+            }
+        }
+    }
+
+    /// Like [`Self::recv_message`], but erroring out if nothing — not even a keepalive —
+    /// arrives within the negotiated `peer_timeout`, per TCPCL's idle-connection teardown.
+    pub async fn recv_message_with_timeout<S: AsyncReadExt + Unpin>(
+        &mut self,
+        stream: &mut S,
+    ) -> BpResult<TcpClMessage> {
+        let idle_limit = Duration::from_secs(self.peer_timeout.max(1) as u64);
+        match tokio::time::timeout(idle_limit, self.recv_message(stream)).await {
+            Ok(result) => result,
+            Err(_) => {
+                self.state = SessionState::Closing;
+                Err(BpError::Protocol("TCPCL session idle timeout".to_string()))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::duplex;
+
+    #[test]
+    fn test_contact_header_round_trip() {
+        let header = ContactHeader::new("ipn:1.1", 30, 90);
+        let encoded = header.encode();
+        let decoded = ContactHeader::decode(&encoded).unwrap();
+        assert_eq!(decoded, header);
+    }
+
+    #[test]
+    fn test_contact_header_rejects_bad_magic() {
+        assert!(ContactHeader::decode(b"nope").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_handshake_negotiates_peer_eid() {
+        let (mut a_stream, mut b_stream) = duplex(4096);
+        let mut a = TcpClSession::new("ipn:1.1", 30, 4096);
+        let mut b = TcpClSession::new("ipn:2.1", 30, 4096);
+
+        let (a_res, b_res) = tokio::join!(
+            a.connect_handshake(&mut a_stream),
+            b.accept_handshake(&mut b_stream)
+        );
+        a_res.unwrap();
+        b_res.unwrap();
+
+        assert_eq!(a.state, SessionState::SessionEstablished);
+        assert_eq!(b.state, SessionState::SessionEstablished);
+        assert_eq!(a.peer_eid.as_deref(), Some("ipn:2.1"));
+        assert_eq!(b.peer_eid.as_deref(), Some("ipn:1.1"));
+    }
+
+    #[tokio::test]
+    async fn test_handshake_negotiates_minimum_keepalive_and_timeout() {
+        let (mut a_stream, mut b_stream) = duplex(4096);
+        let mut a = TcpClSession::with_peer_timeout("ipn:1.1", 60, 200, 4096);
+        let mut b = TcpClSession::with_peer_timeout("ipn:2.1", 15, 50, 4096);
+
+        let (a_event, b_event) = tokio::join!(
+            a.connect_handshake(&mut a_stream),
+            b.accept_handshake(&mut b_stream)
+        );
+
+        assert_eq!(
+            a_event.unwrap(),
+            SessionEvent::Up {
+                peer_eid: "ipn:2.1".to_string(),
+                keepalive_interval: 15,
+                peer_timeout: 50,
+                peer_certificate: None,
+            }
+        );
+        assert_eq!(
+            b_event.unwrap(),
+            SessionEvent::Up {
+                peer_eid: "ipn:1.1".to_string(),
+                keepalive_interval: 15,
+                peer_timeout: 50,
+                peer_certificate: None,
+            }
+        );
+        assert_eq!(a.keepalive_interval, 15);
+        assert_eq!(a.peer_timeout, 50);
+        assert_eq!(b.keepalive_interval, 15);
+        assert_eq!(b.peer_timeout, 50);
+    }
+
+    #[tokio::test]
+    async fn test_recv_with_timeout_errors_when_peer_goes_quiet() {
+        let (mut a_stream, mut b_stream) = duplex(4096);
+        let mut a = TcpClSession::with_peer_timeout("ipn:1.1", 30, 1, 4096);
+        let mut b = TcpClSession::with_peer_timeout("ipn:2.1", 30, 1, 4096);
+
+        let (a_res, b_res) = tokio::join!(
+            a.connect_handshake(&mut a_stream),
+            b.accept_handshake(&mut b_stream)
+        );
+        a_res.unwrap();
+        b_res.unwrap();
+
+        // Neither side sends anything else, so `b` should time out waiting on its
+        // 1-second negotiated peer_timeout rather than hanging forever.
+        let result = b.recv_message_with_timeout(&mut b_stream).await;
+        assert!(result.is_err());
+        assert_eq!(b.state, SessionState::Closing);
+    }
+
+    #[tokio::test]
+    async fn test_segmented_transfer_reassembles() {
+        let (mut a_stream, mut b_stream) = duplex(65536);
+        let mut a = TcpClSession::new("ipn:1.1", 30, 8);
+        let mut b = TcpClSession::new("ipn:2.1", 30, 8);
+
+        let (a_res, b_res) =
+            tokio::join!(a.connect_handshake(&mut a_stream), b.accept_handshake(&mut b_stream));
+        a_res.unwrap();
+        b_res.unwrap();
+
+        let payload = b"this payload is longer than one segment".to_vec();
+        let payload_clone = payload.clone();
+        let (send_res, recv_res) = tokio::join!(
+            a.send_bundle(&mut a_stream, &payload_clone),
+            b.recv_message(&mut b_stream)
+        );
+        send_res.unwrap();
+
+        match recv_res.unwrap() {
+            TcpClMessage::Bundle(data) => assert_eq!(data.to_vec(), payload),
+            other => panic!("expected Bundle, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_recv_rejects_segment_over_max_size() {
+        let (mut a_stream, mut b_stream) = duplex(65536);
+        let mut a = TcpClSession::new("ipn:1.1", 30, 4096);
+        let mut b = TcpClSession::new("ipn:2.1", 30, 8);
+
+        let (a_res, b_res) =
+            tokio::join!(a.connect_handshake(&mut a_stream), b.accept_handshake(&mut b_stream));
+        a_res.unwrap();
+        b_res.unwrap();
+
+        // `a`'s 4096-byte limit lets it send the payload as one segment, bigger than what `b`
+        // will accept; `b` should reject it instead of allocating a buffer for the claimed length.
+        let payload = b"this payload is longer than b's max segment size".to_vec();
+        let (send_res, recv_res) =
+            tokio::join!(a.send_bundle(&mut a_stream, &payload), b.recv_message(&mut b_stream));
+        send_res.unwrap();
+        assert!(recv_res.is_err());
+    }
+}
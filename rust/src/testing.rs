@@ -3,11 +3,22 @@ use crate::{
     metrics::{MetricsCollector, LatencyMeasurement},
     error::{BpError, BpResult},
 };
-use chrono::Utc;
-use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+use rand::{rngs::SmallRng, Rng, SeedableRng};
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::time::{sleep, timeout};
+use uuid::Uuid;
+
+/// Simulation loop tick, in milliseconds. Also the window a [`NodeNetworkCapacity`] budgets
+/// bandwidth over: `capacity_per_step = bandwidth_bps * STEP_MILLIS / 1000`.
+const STEP_MILLIS: u64 = 100;
+
+/// RNG seed used by scenarios that don't pick their own, so existing scenarios keep replaying
+/// identically.
+const DEFAULT_SEED: u64 = 42;
 
 pub struct TestScenario {
     pub name: String,
@@ -16,6 +27,83 @@ pub struct TestScenario {
     pub topology: TestTopology,
     pub traffic_pattern: TrafficPattern,
     pub expected_results: TestResults,
+    /// Seeds the `SmallRng` driving mobility and random-topology decisions for this scenario's
+    /// run, so a failing run can be replayed bit-for-bit by reusing the same seed.
+    pub seed: u64,
+    /// Per-node traffic generators for this run. Empty means `TestHarness::run_scenario` builds
+    /// them from `traffic_pattern`; set via [`TestScenario::with_traffic_generators`] to register
+    /// custom [`Traffic`] impls instead.
+    pub traffic_generators: Vec<Box<dyn Traffic>>,
+    /// DTN routing strategy the store-and-forward subsystem uses to relay bundles across hops
+    /// the topology doesn't connect directly.
+    pub routing_strategy: SimRoutingStrategy,
+    /// Scheduled contact windows overriding `topology`'s range-based connectivity. `None` keeps
+    /// the existing instantaneous-geometry behavior.
+    pub contact_plan: Option<TestContactPlan>,
+}
+
+impl TestScenario {
+    /// Override the per-node traffic generators this scenario runs with, instead of the ones
+    /// `run_scenario` would otherwise build from `traffic_pattern`.
+    pub fn with_traffic_generators(mut self, generators: Vec<Box<dyn Traffic>>) -> Self {
+        self.traffic_generators = generators;
+        self
+    }
+}
+
+/// A node's bandwidth ceiling for one simulation step, derived from a scenario's
+/// `TestTopology::bandwidth_bps`. `send_bundle` reserves against `consumed` and drops the send
+/// if it would exceed `capacity_per_step`; `TestHarness::run_scenario`'s loop resets `consumed`
+/// at the top of every step.
+#[derive(Debug)]
+struct NodeNetworkCapacity {
+    capacity_per_step: AtomicU64,
+    consumed: AtomicU64,
+}
+
+impl NodeNetworkCapacity {
+    fn new(capacity_per_step: u64) -> Self {
+        Self {
+            capacity_per_step: AtomicU64::new(capacity_per_step),
+            consumed: AtomicU64::new(0),
+        }
+    }
+
+    fn set_capacity(&self, capacity_per_step: u64) {
+        self.capacity_per_step.store(capacity_per_step, Ordering::Relaxed);
+    }
+
+    fn reset(&self) {
+        self.consumed.store(0, Ordering::Relaxed);
+    }
+
+    /// Reserve `bytes` against this step's credit; returns `false` (reserving nothing) if doing
+    /// so would exceed `capacity_per_step`.
+    fn try_consume(&self, bytes: u64) -> bool {
+        let capacity = self.capacity_per_step.load(Ordering::Relaxed);
+        loop {
+            let consumed = self.consumed.load(Ordering::Relaxed);
+            if consumed.saturating_add(bytes) > capacity {
+                return false;
+            }
+            if self
+                .consumed
+                .compare_exchange_weak(consumed, consumed + bytes, Ordering::Relaxed, Ordering::Relaxed)
+                .is_ok()
+            {
+                return true;
+            }
+        }
+    }
+}
+
+impl Clone for NodeNetworkCapacity {
+    fn clone(&self) -> Self {
+        Self {
+            capacity_per_step: AtomicU64::new(self.capacity_per_step.load(Ordering::Relaxed)),
+            consumed: AtomicU64::new(self.consumed.load(Ordering::Relaxed)),
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -26,6 +114,7 @@ pub struct TestNode {
     pub metrics: Option<Arc<MetricsCollector>>,
     pub position: (f64, f64),
     pub mobility: Option<MobilityModel>,
+    capacity: NodeNetworkCapacity,
 }
 
 impl TestNode {
@@ -37,9 +126,21 @@ impl TestNode {
             metrics: None,
             position: (0.0, 0.0),
             mobility: None,
+            capacity: NodeNetworkCapacity::new(u64::MAX),
         }
     }
 
+    /// Set this node's per-step byte credit, e.g. from `TestTopology::bandwidth_bps` at the
+    /// start of a scenario run.
+    pub fn set_capacity_per_step(&self, bytes_per_step: u64) {
+        self.capacity.set_capacity(bytes_per_step);
+    }
+
+    /// Clear consumed bandwidth for a new simulation step.
+    pub fn reset_capacity(&self) {
+        self.capacity.reset();
+    }
+
     pub fn with_position(mut self, x: f64, y: f64) -> Self {
         self.position = (x, y);
         self
@@ -64,16 +165,22 @@ impl TestNode {
         Ok(())
     }
 
-    pub async fn send_bundle(&self, dest: &Eid, payload: &str) -> BpResult<()> {
+    /// Send a bundle, subject to this node's per-step bandwidth credit. Returns `Ok(false)`
+    /// without sending if `payload` would exceed the remaining credit for this step.
+    pub async fn send_bundle(&self, dest: &Eid, payload: &str) -> BpResult<bool> {
+        if !self.capacity.try_consume(payload.len() as u64) {
+            return Ok(false);
+        }
+
         if let Some(sdk) = &self.sdk {
             let bundle = Bundle::new(self.eid.clone(), dest.clone(), payload.to_string());
             sdk.send(bundle).await?;
-            
+
             if let Some(metrics) = &self.metrics {
                 metrics.record_bundle_sent(payload.len() as u64);
             }
         }
-        Ok(())
+        Ok(true)
     }
 
     pub async fn receive_bundle(&self, timeout_duration: Duration) -> BpResult<Bundle> {
@@ -106,19 +213,11 @@ pub enum MobilityModel {
 }
 
 impl MobilityModel {
-    pub fn update_position(&self, current: (f64, f64), time_delta: f64) -> (f64, f64) {
+    pub fn update_position(&self, current: (f64, f64), time_delta: f64, rng: &mut SmallRng) -> (f64, f64) {
         match self {
             MobilityModel::Static => current,
             MobilityModel::RandomWalk { speed, bounds } => {
-                use std::collections::hash_map::DefaultHasher;
-                use std::hash::{Hash, Hasher};
-                
-                let mut hasher = DefaultHasher::new();
-                current.0.to_bits().hash(&mut hasher);
-                current.1.to_bits().hash(&mut hasher);
-                let hash = hasher.finish();
-                
-                let angle = (hash as f64 / u64::MAX as f64) * 2.0 * std::f64::consts::PI;
+                let angle = rng.gen_range(0.0..(2.0 * std::f64::consts::PI));
                 let distance = speed * time_delta;
                 let new_x = (current.0 + distance * angle.cos()).clamp(bounds.0, bounds.2);
                 let new_y = (current.1 + distance * angle.sin()).clamp(bounds.1, bounds.3);
@@ -159,7 +258,7 @@ pub enum TopologyType {
 }
 
 impl TestTopology {
-    pub fn can_communicate(&self, node1: &TestNode, node2: &TestNode) -> bool {
+    pub fn can_communicate(&self, node1: &TestNode, node2: &TestNode, rng: &mut SmallRng) -> bool {
         match self.topology_type {
             TopologyType::FullyConnected => true,
             TopologyType::Linear => {
@@ -180,14 +279,49 @@ impl TestTopology {
             }
             TopologyType::Random { connection_probability } => {
                 let distance = node1.distance_to(node2);
-                let deterministic_value = ((node1.position.0 + node2.position.0) * 1000.0) % 1.0;
-                distance <= self.communication_range && deterministic_value <= connection_probability
+                distance <= self.communication_range && rng.gen::<f64>() <= connection_probability
             }
         }
     }
 }
 
+/// One scheduled link window in a [`TestContactPlan`], e.g. a satellite pass: `node_a` and
+/// `node_b` can only reach each other between `start` and `end` (both relative to scenario
+/// start), and at that contact's own bandwidth/latency rather than the topology defaults.
 #[derive(Debug, Clone)]
+pub struct ScheduledContact {
+    pub node_a: usize,
+    pub node_b: usize,
+    pub start: Duration,
+    pub end: Duration,
+    pub bandwidth_bps: u64,
+    pub latency_ms: u64,
+}
+
+/// A time-varying connectivity schedule for a [`TestScenario`], replacing instantaneous
+/// range-based [`TestTopology::can_communicate`] checks with scheduled contact windows.
+#[derive(Debug, Clone, Default)]
+pub struct TestContactPlan {
+    pub contacts: Vec<ScheduledContact>,
+}
+
+impl TestContactPlan {
+    pub fn new(contacts: Vec<ScheduledContact>) -> Self {
+        Self { contacts }
+    }
+
+    /// The scheduled contact (if any) covering `node_a`/`node_b` in either order at `elapsed`
+    /// simulated time since scenario start.
+    pub fn link_at(&self, node_a: usize, node_b: usize, elapsed: Duration) -> Option<&ScheduledContact> {
+        self.contacts.iter().find(|c| {
+            ((c.node_a == node_a && c.node_b == node_b) || (c.node_a == node_b && c.node_b == node_a))
+                && elapsed >= c.start
+                && elapsed <= c.end
+        })
+    }
+}
+
+#[derive(Clone)]
 pub struct TrafficPattern {
     pub pattern_type: TrafficType,
     pub message_size: usize,
@@ -195,6 +329,24 @@ pub struct TrafficPattern {
     pub total_messages: usize,
     pub priority: Priority,
     pub custody: Custody,
+    /// Overrides destination selection for every node's default generator, decoupling "who to
+    /// send to" (this) from "how much/when to send" (`pattern_type`'s other fields). `None` keeps
+    /// the old per-`TrafficType` destination logic.
+    pub destination_pattern: Option<Arc<dyn Pattern>>,
+}
+
+impl std::fmt::Debug for TrafficPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TrafficPattern")
+            .field("pattern_type", &self.pattern_type)
+            .field("message_size", &self.message_size)
+            .field("message_interval", &self.message_interval)
+            .field("total_messages", &self.total_messages)
+            .field("priority", &self.priority)
+            .field("custody", &self.custody)
+            .field("destination_pattern", &self.destination_pattern.as_ref().map(|_| "Arc<dyn Pattern>"))
+            .finish()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -206,6 +358,385 @@ pub enum TrafficType {
     Random,
 }
 
+/// One decision a node's [`Traffic`] generator makes when polled for a simulation step.
+#[derive(Debug, Clone)]
+pub enum TrafficAction {
+    /// Send `size` bytes to node `dest` this step.
+    Generate { dest: usize, size: usize },
+    /// Generate nothing this step, but keep being polled (e.g. waiting on a reply).
+    WaitData,
+    /// Generate nothing until `Instant` has passed.
+    WaitUntil(Instant),
+    /// Stop generating for the rest of the run; the node still receives.
+    FinishedGenerating,
+}
+
+/// A per-node traffic source. `TestHarness::run_scenario` polls one of these for every node that
+/// hasn't returned `FinishedGenerating`, once per simulation step. Implement this to model
+/// workloads the fixed [`TrafficType`] patterns can't, such as request/response traffic that
+/// waits after sending or a generator that stops producing but keeps receiving.
+pub trait Traffic: Send {
+    fn next_action(&mut self, node_idx: usize, now: Instant, rng: &mut SmallRng) -> TrafficAction;
+}
+
+/// Never generates. Fills in for nodes that only receive under a pattern scoped to other nodes,
+/// e.g. the destination in `AllToOne`, or every node but the source in `OneToOne`.
+struct NullTraffic;
+
+impl Traffic for NullTraffic {
+    fn next_action(&mut self, _node_idx: usize, _now: Instant, _rng: &mut SmallRng) -> TrafficAction {
+        TrafficAction::FinishedGenerating
+    }
+}
+
+/// Sends `message_size`-byte messages to a fixed `dest` every `interval`, up to `remaining`
+/// times. Backs `OneToOne`'s source node and each sending node of `AllToOne`.
+struct FixedDestTraffic {
+    dest: usize,
+    message_size: usize,
+    interval: Duration,
+    remaining: usize,
+    next_at: Option<Instant>,
+}
+
+impl Traffic for FixedDestTraffic {
+    fn next_action(&mut self, _node_idx: usize, now: Instant, _rng: &mut SmallRng) -> TrafficAction {
+        if self.remaining == 0 {
+            return TrafficAction::FinishedGenerating;
+        }
+        if let Some(next_at) = self.next_at {
+            if now < next_at {
+                return TrafficAction::WaitUntil(next_at);
+            }
+        }
+
+        self.remaining -= 1;
+        self.next_at = Some(now + self.interval);
+        TrafficAction::Generate { dest: self.dest, size: self.message_size }
+    }
+}
+
+/// Cycles through `targets` every `interval`, up to `remaining` sends total. Backs `OneToAll`
+/// (targets = every other node) and each node's instance under `AllToAll`.
+struct CyclingTraffic {
+    targets: Vec<usize>,
+    next_target: usize,
+    message_size: usize,
+    interval: Duration,
+    remaining: usize,
+    next_at: Option<Instant>,
+}
+
+impl Traffic for CyclingTraffic {
+    fn next_action(&mut self, _node_idx: usize, now: Instant, _rng: &mut SmallRng) -> TrafficAction {
+        if self.remaining == 0 || self.targets.is_empty() {
+            return TrafficAction::FinishedGenerating;
+        }
+        if let Some(next_at) = self.next_at {
+            if now < next_at {
+                return TrafficAction::WaitUntil(next_at);
+            }
+        }
+
+        let dest = self.targets[self.next_target];
+        self.next_target = (self.next_target + 1) % self.targets.len();
+        self.remaining -= 1;
+        self.next_at = Some(now + self.interval);
+        TrafficAction::Generate { dest, size: self.message_size }
+    }
+}
+
+/// Chooses a destination for a generated message, decoupling "who to send to" from [`Traffic`]'s
+/// "how much/when to send". Built-in impls below cover uniform-random, permutation, hotspot, and
+/// neighbor-only communication; register a custom one via [`TrafficPattern::destination_pattern`].
+pub trait Pattern: Send + Sync {
+    fn target(&self, source: usize, node_count: usize, rng: &mut SmallRng) -> usize;
+}
+
+/// Uniformly random destination other than `source`.
+pub struct UniformPattern;
+
+impl Pattern for UniformPattern {
+    fn target(&self, source: usize, node_count: usize, rng: &mut SmallRng) -> usize {
+        if node_count < 2 {
+            return source;
+        }
+        let mut dest = rng.gen_range(0..node_count);
+        while dest == source {
+            dest = rng.gen_range(0..node_count);
+        }
+        dest
+    }
+}
+
+/// Maps each source to one fixed destination, shuffled at construction time so the mapping isn't
+/// just identity. Sources with no other node to map to (a single-node scenario) map to themselves.
+pub struct RandomPermutationPattern {
+    mapping: Vec<usize>,
+}
+
+impl RandomPermutationPattern {
+    pub fn new(node_count: usize, rng: &mut SmallRng) -> Self {
+        let mut mapping: Vec<usize> = (0..node_count).collect();
+        for i in (1..node_count).rev() {
+            let j = rng.gen_range(0..=i);
+            mapping.swap(i, j);
+        }
+        for i in 0..node_count {
+            if mapping[i] == i && node_count > 1 {
+                let j = (i + 1) % node_count;
+                mapping.swap(i, j);
+            }
+        }
+        Self { mapping }
+    }
+}
+
+impl Pattern for RandomPermutationPattern {
+    fn target(&self, source: usize, _node_count: usize, _rng: &mut SmallRng) -> usize {
+        self.mapping.get(source).copied().unwrap_or(source)
+    }
+}
+
+/// Sends to one of `hotspots` with probability `hotspot_weight` (uniform among them, excluding
+/// `source`), otherwise falls back to [`UniformPattern`] over every node.
+pub struct HotspotPattern {
+    hotspots: Vec<usize>,
+    hotspot_weight: f64,
+}
+
+impl HotspotPattern {
+    pub fn new(hotspots: Vec<usize>, hotspot_weight: f64) -> Self {
+        Self { hotspots, hotspot_weight }
+    }
+}
+
+impl Pattern for HotspotPattern {
+    fn target(&self, source: usize, node_count: usize, rng: &mut SmallRng) -> usize {
+        if !self.hotspots.is_empty() && rng.gen::<f64>() < self.hotspot_weight {
+            let candidates: Vec<usize> = self.hotspots.iter().copied().filter(|&n| n != source).collect();
+            if !candidates.is_empty() {
+                return candidates[rng.gen_range(0..candidates.len())];
+            }
+        }
+        UniformPattern.target(source, node_count, rng)
+    }
+}
+
+/// Picks among nodes within `communication_range` of `source`'s position, falling back to
+/// [`UniformPattern`] if none are in range. `positions` is a snapshot taken when this pattern is
+/// built, so it doesn't track nodes that move mid-run.
+pub struct LocalNeighborPattern {
+    positions: Vec<(f64, f64)>,
+    communication_range: f64,
+}
+
+impl LocalNeighborPattern {
+    pub fn new(positions: Vec<(f64, f64)>, communication_range: f64) -> Self {
+        Self { positions, communication_range }
+    }
+}
+
+impl Pattern for LocalNeighborPattern {
+    fn target(&self, source: usize, node_count: usize, rng: &mut SmallRng) -> usize {
+        let (sx, sy) = match self.positions.get(source) {
+            Some(&position) => position,
+            None => return UniformPattern.target(source, node_count, rng),
+        };
+
+        let neighbors: Vec<usize> = self.positions.iter().enumerate()
+            .filter(|(idx, (x, y))| {
+                *idx != source && {
+                    let dx = x - sx;
+                    let dy = y - sy;
+                    (dx * dx + dy * dy).sqrt() <= self.communication_range
+                }
+            })
+            .map(|(idx, _)| idx)
+            .collect();
+
+        if neighbors.is_empty() {
+            return UniformPattern.target(source, node_count, rng);
+        }
+        neighbors[rng.gen_range(0..neighbors.len())]
+    }
+}
+
+/// Sends every `interval` to a destination chosen by `pattern`, up to `remaining` times. Backs
+/// `TrafficType::Random`'s default (a [`UniformPattern`]) and any pattern that sets
+/// `TrafficPattern::destination_pattern`.
+struct PatternTraffic {
+    pattern: Arc<dyn Pattern>,
+    node_count: usize,
+    message_size: usize,
+    interval: Duration,
+    remaining: usize,
+    next_at: Option<Instant>,
+}
+
+impl Traffic for PatternTraffic {
+    fn next_action(&mut self, node_idx: usize, now: Instant, rng: &mut SmallRng) -> TrafficAction {
+        if self.remaining == 0 || self.node_count < 2 {
+            return TrafficAction::FinishedGenerating;
+        }
+        if let Some(next_at) = self.next_at {
+            if now < next_at {
+                return TrafficAction::WaitUntil(next_at);
+            }
+        }
+
+        let dest = self.pattern.target(node_idx, self.node_count, rng);
+        self.remaining -= 1;
+        self.next_at = Some(now + self.interval);
+        TrafficAction::Generate { dest, size: self.message_size }
+    }
+}
+
+enum RequestResponseState {
+    Ready,
+    AwaitingReply { since: Instant },
+}
+
+/// Example request/response generator: sends to `dest`, then parks in `WaitData` until
+/// `reply_timeout` has elapsed before sending the next request. There's no live reply-delivery
+/// hook yet, so this approximates "wait for the reply" with a fixed settle time rather than
+/// reacting to an actual inbound bundle.
+pub struct RequestResponseTraffic {
+    dest: usize,
+    message_size: usize,
+    reply_timeout: Duration,
+    remaining: usize,
+    state: RequestResponseState,
+}
+
+impl RequestResponseTraffic {
+    pub fn new(dest: usize, message_size: usize, reply_timeout: Duration, total_messages: usize) -> Self {
+        Self {
+            dest,
+            message_size,
+            reply_timeout,
+            remaining: total_messages,
+            state: RequestResponseState::Ready,
+        }
+    }
+}
+
+impl Traffic for RequestResponseTraffic {
+    fn next_action(&mut self, _node_idx: usize, now: Instant, _rng: &mut SmallRng) -> TrafficAction {
+        if let RequestResponseState::AwaitingReply { since } = self.state {
+            if now.duration_since(since) < self.reply_timeout {
+                return TrafficAction::WaitData;
+            }
+            self.state = RequestResponseState::Ready;
+        }
+
+        if self.remaining == 0 {
+            return TrafficAction::FinishedGenerating;
+        }
+
+        self.remaining -= 1;
+        self.state = RequestResponseState::AwaitingReply { since: now };
+        TrafficAction::Generate { dest: self.dest, size: self.message_size }
+    }
+}
+
+/// Drives one node's [`Traffic`] generator across simulation steps: caches any `WaitUntil`
+/// deadline and latches `FinishedGenerating` so a waiting or finished generator isn't re-polled
+/// every step.
+struct ServerTrafficState {
+    generator: Box<dyn Traffic>,
+    wait_until: Option<Instant>,
+    finished: bool,
+}
+
+impl ServerTrafficState {
+    fn new(generator: Box<dyn Traffic>) -> Self {
+        Self { generator, wait_until: None, finished: false }
+    }
+
+    /// Poll this node's generator if it's due, returning `Some((dest, size))` to send this step.
+    fn poll(&mut self, node_idx: usize, now: Instant, rng: &mut SmallRng) -> Option<(usize, usize)> {
+        if self.finished {
+            return None;
+        }
+        if let Some(wait_until) = self.wait_until {
+            if now < wait_until {
+                return None;
+            }
+            self.wait_until = None;
+        }
+
+        match self.generator.next_action(node_idx, now, rng) {
+            TrafficAction::Generate { dest, size } => Some((dest, size)),
+            TrafficAction::WaitData => None,
+            TrafficAction::WaitUntil(until) => {
+                self.wait_until = Some(until);
+                None
+            }
+            TrafficAction::FinishedGenerating => {
+                self.finished = true;
+                None
+            }
+        }
+    }
+}
+
+/// Build one [`Traffic`] generator per node, reimplementing `pattern.pattern_type` the way the
+/// old fixed-pattern dispatcher used to, for scenarios that don't register their own via
+/// [`TestScenario::with_traffic_generators`].
+fn default_traffic_generators(pattern: &TrafficPattern, node_count: usize) -> Vec<Box<dyn Traffic>> {
+    let message_size = pattern.message_size;
+    let interval = pattern.message_interval;
+    let total_messages = pattern.total_messages;
+
+    if let Some(destination_pattern) = &pattern.destination_pattern {
+        return (0..node_count)
+            .map(|_| -> Box<dyn Traffic> {
+                Box::new(PatternTraffic {
+                    pattern: destination_pattern.clone(),
+                    node_count,
+                    message_size,
+                    interval,
+                    remaining: total_messages,
+                    next_at: None,
+                })
+            })
+            .collect();
+    }
+
+    (0..node_count)
+        .map(|node_idx| -> Box<dyn Traffic> {
+            match pattern.pattern_type {
+                TrafficType::OneToOne { source, destination } if node_idx == source && destination < node_count => {
+                    Box::new(FixedDestTraffic { dest: destination, message_size, interval, remaining: total_messages, next_at: None })
+                }
+                TrafficType::OneToAll { source } if node_idx == source => {
+                    let targets = (0..node_count).filter(|&n| n != node_idx).collect();
+                    Box::new(CyclingTraffic { targets, next_target: 0, message_size, interval, remaining: total_messages, next_at: None })
+                }
+                TrafficType::AllToOne { destination } if node_idx != destination && destination < node_count => {
+                    Box::new(FixedDestTraffic { dest: destination, message_size, interval, remaining: total_messages, next_at: None })
+                }
+                TrafficType::AllToAll => {
+                    let targets = (0..node_count).filter(|&n| n != node_idx).collect();
+                    Box::new(CyclingTraffic { targets, next_target: 0, message_size, interval, remaining: total_messages, next_at: None })
+                }
+                TrafficType::Random => {
+                    Box::new(PatternTraffic {
+                        pattern: Arc::new(UniformPattern),
+                        node_count,
+                        message_size,
+                        interval,
+                        remaining: total_messages,
+                        next_at: None,
+                    })
+                }
+                _ => Box::new(NullTraffic),
+            }
+        })
+        .collect()
+}
+
 #[derive(Debug, Clone)]
 pub struct TestResults {
     pub expected_delivery_ratio: f64,
@@ -255,6 +786,7 @@ impl TestHarness {
                 total_messages: 100,
                 priority: Priority::Standard,
                 custody: Custody::None,
+                destination_pattern: None,
             },
             expected_results: TestResults {
                 expected_delivery_ratio: 0.95,
@@ -262,6 +794,10 @@ impl TestHarness {
                 min_throughput_bps: 100_000.0,
                 max_overhead_ratio: 0.1,
             },
+            seed: DEFAULT_SEED,
+            traffic_generators: Vec::new(),
+            routing_strategy: SimRoutingStrategy::Epidemic,
+            contact_plan: None,
         });
 
         self.add_scenario(TestScenario {
@@ -295,6 +831,7 @@ impl TestHarness {
                 total_messages: 50,
                 priority: Priority::Standard,
                 custody: Custody::Optional,
+                destination_pattern: None,
             },
             expected_results: TestResults {
                 expected_delivery_ratio: 0.7,
@@ -302,6 +839,10 @@ impl TestHarness {
                 min_throughput_bps: 10_000.0,
                 max_overhead_ratio: 0.5,
             },
+            seed: DEFAULT_SEED,
+            traffic_generators: Vec::new(),
+            routing_strategy: SimRoutingStrategy::Epidemic,
+            contact_plan: None,
         });
 
         Ok(())
@@ -321,24 +862,61 @@ impl TestHarness {
             node.initialize().await?;
         }
 
+        let capacity_per_step = self.scenarios[scenario_index].topology.bandwidth_bps * STEP_MILLIS / 1000;
+        for node in &self.scenarios[scenario_index].nodes {
+            node.set_capacity_per_step(capacity_per_step);
+        }
+
+        let mut rng = SmallRng::seed_from_u64(self.scenarios[scenario_index].seed);
+
+        let node_count = self.scenarios[scenario_index].nodes.len();
+        let generators = if self.scenarios[scenario_index].traffic_generators.is_empty() {
+            default_traffic_generators(&self.scenarios[scenario_index].traffic_pattern, node_count)
+        } else {
+            std::mem::take(&mut self.scenarios[scenario_index].traffic_generators)
+        };
+        let mut traffic_states: Vec<ServerTrafficState> = generators.into_iter().map(ServerTrafficState::new).collect();
+
         let start_time = Utc::now();
         let mut sent_messages = 0;
         let mut received_messages = 0;
         let mut latency_measurements = Vec::new();
+        let mut pending_deliveries = Vec::new();
+        let mut in_flight: Vec<InFlightBundle> = Vec::new();
+        let mut total_hops: u64 = 0;
+        let mut total_copies: usize = 0;
 
         let execution_duration = Duration::from_secs(30);
         let end_time = start_time + chrono::Duration::from_std(execution_duration).unwrap();
 
         while Utc::now() < end_time {
-            execute_traffic_pattern(&mut self.scenarios[scenario_index], &mut sent_messages, &mut received_messages).await?;
-            update_node_positions(&mut self.scenarios[scenario_index]).await;
+            for node in &self.scenarios[scenario_index].nodes {
+                node.reset_capacity();
+            }
+
+            let elapsed = (Utc::now() - start_time).to_std().unwrap_or(Duration::ZERO);
+            let adjacency = build_adjacency(&self.scenarios[scenario_index], elapsed, &mut rng);
+
+            execute_traffic(&self.scenarios[scenario_index], &mut traffic_states, &mut sent_messages, &mut in_flight, &mut rng).await?;
+            forward_step(
+                &self.scenarios[scenario_index],
+                &adjacency,
+                elapsed,
+                &mut in_flight,
+                &mut pending_deliveries,
+                &mut total_hops,
+                &mut total_copies,
+                &mut rng,
+            ).await?;
+            update_node_positions(&mut self.scenarios[scenario_index], &mut rng).await;
             collect_latency_measurements(&self.scenarios[scenario_index], &mut latency_measurements).await;
-            
-            sleep(Duration::from_millis(100)).await;
+            process_pending_deliveries(&mut pending_deliveries, &mut received_messages, &mut latency_measurements);
+
+            sleep(Duration::from_millis(STEP_MILLIS)).await;
         }
 
         let scenario = &self.scenarios[scenario_index];
-        let result = analyze_results(scenario, sent_messages, received_messages, latency_measurements).await;
+        let result = analyze_results(scenario, sent_messages, received_messages, latency_measurements, total_hops, total_copies).await;
         self.results.insert(scenario.name.clone(), result.clone());
 
         Ok(result)
@@ -375,69 +953,268 @@ impl TestHarness {
     }
 }
 
-// Standalone helper functions to avoid borrowing conflicts
-async fn execute_traffic_pattern(scenario: &mut TestScenario, sent: &mut usize, _received: &mut usize) -> BpResult<()> {
-    match scenario.traffic_pattern.pattern_type {
-        TrafficType::OneToOne { source, destination } => {
-            if source < scenario.nodes.len() && destination < scenario.nodes.len() {
-                let payload = format!("Message {}", *sent);
-                scenario.nodes[source].send_bundle(&scenario.nodes[destination].eid, &payload).await?;
-                *sent += 1;
+/// A bundle that reached (or is adjacent to) its destination, awaiting virtual arrival at
+/// `deliver_at`. Queued by [`forward_step`] and drained by [`process_pending_deliveries`].
+/// `sent_at` is the bundle's original creation time, not the time of its last hop, so the
+/// resulting [`LatencyMeasurement`] spans the full multi-hop journey.
+struct PendingDelivery {
+    bundle_id: String,
+    sent_at: DateTime<Utc>,
+    deliver_at: DateTime<Utc>,
+}
+
+/// DTN routing strategy the store-and-forward subsystem uses to decide which neighbor(s) a
+/// carrier hands a bundle to each step it can't yet reach the destination directly. Mirrors
+/// [`crate::routing::EpidemicRouting`]/[`crate::routing::SprayAndWaitRouting`] at simulation
+/// scale, over each step's connectivity graph: `contact_plan`'s windows when one is set, else the
+/// topology's instantaneous connectivity.
+#[derive(Debug, Clone)]
+pub enum SimRoutingStrategy {
+    /// Replicate to every currently-reachable neighbor that doesn't already carry a copy.
+    Epidemic,
+    /// Start with `initial_copies` copies; a carrier holding more than one hands half to each new
+    /// contact, until down to a single copy, which is only delivered (never replicated further).
+    SprayAndWait { initial_copies: u32 },
+    /// Forward the single copy one hop along the shortest path over this step's connectivity
+    /// graph, store-and-carrying it when the next hop isn't currently reachable.
+    ShortestPath,
+}
+
+/// A bundle in transit through the store-and-forward subsystem: may exist as several in-network
+/// copies (under `Epidemic`/`SprayAndWait`) until one reaches a node adjacent to `dest`.
+struct InFlightBundle {
+    bundle_id: String,
+    dest: usize,
+    size: usize,
+    created_at: DateTime<Utc>,
+    /// Steps spent in flight so far, for `TestExecutionResult::avg_hop_count`.
+    hops: u32,
+    /// Nodes currently carrying a copy. The value is the carrier's remaining spray count under
+    /// `SprayAndWait`; ignored by `Epidemic` and `ShortestPath`.
+    carriers: HashMap<usize, u32>,
+}
+
+/// Build this step's connectivity graph: `adjacency[i][j]` is whether `i` can currently reach
+/// `j`. When `scenario.contact_plan` is set, a link is up only inside its scheduled contact
+/// window at `elapsed` simulated time; otherwise falls back to `TestTopology::can_communicate`.
+fn build_adjacency(scenario: &TestScenario, elapsed: Duration, rng: &mut SmallRng) -> Vec<Vec<bool>> {
+    let node_count = scenario.nodes.len();
+    let mut adjacency = vec![vec![false; node_count]; node_count];
+    for i in 0..node_count {
+        for j in 0..node_count {
+            if i != j {
+                adjacency[i][j] = match &scenario.contact_plan {
+                    Some(plan) => plan.link_at(i, j, elapsed).is_some(),
+                    None => scenario.topology.can_communicate(&scenario.nodes[i], &scenario.nodes[j], rng),
+                };
             }
         }
-        TrafficType::OneToAll { source } => {
-            if source < scenario.nodes.len() {
-                let payload = format!("Broadcast {}", *sent);
-                for (i, node) in scenario.nodes.iter().enumerate() {
-                    if i != source {
-                        scenario.nodes[source].send_bundle(&node.eid, &payload).await?;
-                    }
-                }
-                *sent += 1;
+    }
+    adjacency
+}
+
+/// Breadth-first search over `adjacency` for the hop after `from` on a shortest path to `to`, or
+/// `None` if `to` isn't currently reachable from `from` at all.
+fn shortest_path_next_hop(adjacency: &[Vec<bool>], from: usize, to: usize) -> Option<usize> {
+    if from == to {
+        return None;
+    }
+
+    let mut visited = vec![false; adjacency.len()];
+    let mut prev = vec![None; adjacency.len()];
+    let mut queue = VecDeque::new();
+    visited[from] = true;
+    queue.push_back(from);
+
+    while let Some(node) = queue.pop_front() {
+        if node == to {
+            break;
+        }
+        for neighbor in 0..adjacency.len() {
+            if adjacency[node][neighbor] && !visited[neighbor] {
+                visited[neighbor] = true;
+                prev[neighbor] = Some(node);
+                queue.push_back(neighbor);
             }
         }
-        TrafficType::AllToOne { destination } => {
-            if destination < scenario.nodes.len() {
-                let payload = format!("Message {}", *sent);
-                for (i, node) in scenario.nodes.iter().enumerate() {
-                    if i != destination {
-                        node.send_bundle(&scenario.nodes[destination].eid, &payload).await?;
+    }
+
+    if !visited[to] {
+        return None;
+    }
+
+    let mut step = to;
+    while let Some(p) = prev[step] {
+        if p == from {
+            return Some(step);
+        }
+        step = p;
+    }
+    None
+}
+
+/// Advance every in-flight bundle by one step: deliver it if any carrier is now adjacent to
+/// `dest` (subject to the channel's `link_reliability` roll and serialization delay, as before),
+/// otherwise replicate or carry it onward per `scenario.routing_strategy`. Successful deliveries
+/// are queued onto `pending` rather than counted directly, preserving the existing emulated
+/// latency/serialization modeling for the final hop. When `scenario.contact_plan` governs a hop,
+/// that contact's own bandwidth/latency are used for the serialization delay instead of
+/// `topology`'s defaults.
+async fn forward_step(
+    scenario: &TestScenario,
+    adjacency: &[Vec<bool>],
+    elapsed: Duration,
+    in_flight: &mut Vec<InFlightBundle>,
+    pending: &mut Vec<PendingDelivery>,
+    total_hops: &mut u64,
+    total_copies: &mut usize,
+    rng: &mut SmallRng,
+) -> BpResult<()> {
+    let mut still_in_flight = Vec::with_capacity(in_flight.len());
+
+    for mut bundle in in_flight.drain(..) {
+        let carriers: Vec<usize> = bundle.carriers.keys().copied().collect();
+        let mut delivered = false;
+
+        for carrier in &carriers {
+            let payload = "x".repeat(bundle.size);
+            if adjacency[*carrier][bundle.dest]
+                && scenario.nodes[*carrier].send_bundle(&scenario.nodes[bundle.dest].eid, &payload).await?
+                && rng.gen::<f64>() <= scenario.topology.link_reliability
+            {
+                let (bandwidth_bps, latency_ms) = match scenario.contact_plan.as_ref().and_then(|p| p.link_at(*carrier, bundle.dest, elapsed)) {
+                    Some(contact) => (contact.bandwidth_bps, contact.latency_ms as f64),
+                    None => (scenario.topology.bandwidth_bps, scenario.topology.latency_ms),
+                };
+                let serialization_ms = bundle.size as f64 * 8.0 / bandwidth_bps as f64 * 1000.0;
+                pending.push(PendingDelivery {
+                    bundle_id: bundle.bundle_id.clone(),
+                    sent_at: bundle.created_at,
+                    deliver_at: Utc::now() + chrono::Duration::milliseconds((latency_ms + serialization_ms) as i64),
+                });
+                *total_hops += bundle.hops as u64 + 1;
+                delivered = true;
+                break;
+            }
+        }
+
+        if delivered {
+            continue;
+        }
+
+        match &scenario.routing_strategy {
+            SimRoutingStrategy::Epidemic => {
+                for &carrier in &carriers {
+                    for neighbor in 0..scenario.nodes.len() {
+                        if adjacency[carrier][neighbor] && !bundle.carriers.contains_key(&neighbor) {
+                            bundle.carriers.insert(neighbor, 0);
+                            *total_copies += 1;
+                        }
                     }
                 }
-                *sent += 1;
             }
-        }
-        TrafficType::AllToAll => {
-            let payload = format!("AllToAll {}", *sent);
-            for (i, sender) in scenario.nodes.iter().enumerate() {
-                for (j, receiver) in scenario.nodes.iter().enumerate() {
-                    if i != j {
-                        sender.send_bundle(&receiver.eid, &payload).await?;
+            SimRoutingStrategy::SprayAndWait { .. } => {
+                for &carrier in &carriers {
+                    let copies = *bundle.carriers.get(&carrier).unwrap_or(&0);
+                    if copies <= 1 {
+                        continue;
+                    }
+                    for neighbor in 0..scenario.nodes.len() {
+                        if adjacency[carrier][neighbor] && !bundle.carriers.contains_key(&neighbor) {
+                            let half = copies / 2;
+                            if half == 0 {
+                                continue;
+                            }
+                            *bundle.carriers.get_mut(&carrier).unwrap() -= half;
+                            bundle.carriers.insert(neighbor, half);
+                            *total_copies += 1;
+                            break;
+                        }
                     }
                 }
             }
-            *sent += 1;
-        }
-        TrafficType::Random => {
-            if scenario.nodes.len() >= 2 {
-                let source = (*sent * 7) % scenario.nodes.len();
-                let mut destination = (*sent * 13) % scenario.nodes.len();
-                while destination == source {
-                    destination = (destination + 1) % scenario.nodes.len();
+            SimRoutingStrategy::ShortestPath => {
+                if let Some(&carrier) = carriers.first() {
+                    if let Some(next_hop) = shortest_path_next_hop(adjacency, carrier, bundle.dest) {
+                        bundle.carriers.remove(&carrier);
+                        bundle.carriers.insert(next_hop, 1);
+                    }
                 }
-                let payload = format!("Random {}", *sent);
-                scenario.nodes[source].send_bundle(&scenario.nodes[destination].eid, &payload).await?;
-                *sent += 1;
             }
         }
+
+        bundle.hops += 1;
+        still_in_flight.push(bundle);
     }
+
+    *in_flight = still_in_flight;
     Ok(())
 }
 
-async fn update_node_positions(scenario: &mut TestScenario) {
+/// Deliver any [`PendingDelivery`] whose virtual arrival time has passed: counts it received and
+/// records a [`LatencyMeasurement`] spanning its emulated transit time.
+fn process_pending_deliveries(pending: &mut Vec<PendingDelivery>, received: &mut usize, measurements: &mut Vec<LatencyMeasurement>) {
+    let now = Utc::now();
+    let (ready, not_ready): (Vec<_>, Vec<_>) = pending.drain(..).partition(|d| d.deliver_at <= now);
+    *pending = not_ready;
+
+    for delivery in ready {
+        *received += 1;
+        measurements.push(LatencyMeasurement::new(delivery.bundle_id, delivery.sent_at));
+    }
+}
+
+/// Poll every node's traffic generator for this step and, for anything it generates, consume the
+/// source's bandwidth credit and hand the bundle to the store-and-forward subsystem as a new
+/// [`InFlightBundle`] (with `routing_strategy`'s initial copy count). Replaces the old
+/// fixed-pattern dispatcher with per-node [`Traffic`] impls.
+async fn execute_traffic(
+    scenario: &TestScenario,
+    states: &mut [ServerTrafficState],
+    sent: &mut usize,
+    in_flight: &mut Vec<InFlightBundle>,
+    rng: &mut SmallRng,
+) -> BpResult<()> {
+    let now = Instant::now();
+    let mut generated = Vec::new();
+    for (node_idx, state) in states.iter_mut().enumerate() {
+        if let Some((dest, size)) = state.poll(node_idx, now, rng) {
+            generated.push((node_idx, dest, size));
+        }
+    }
+
+    let initial_copies = match scenario.routing_strategy {
+        SimRoutingStrategy::SprayAndWait { initial_copies } => initial_copies,
+        _ => 0,
+    };
+
+    for (source, dest, size) in generated {
+        if dest >= scenario.nodes.len() || dest == source {
+            continue;
+        }
+        let payload = "x".repeat(size);
+        if scenario.nodes[source].send_bundle(&scenario.nodes[dest].eid, &payload).await? {
+            *sent += 1;
+            let mut carriers = HashMap::new();
+            carriers.insert(source, initial_copies);
+            in_flight.push(InFlightBundle {
+                bundle_id: Uuid::new_v4().to_string(),
+                dest,
+                size,
+                created_at: Utc::now(),
+                hops: 0,
+                carriers,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+async fn update_node_positions(scenario: &mut TestScenario, rng: &mut SmallRng) {
     for node in &mut scenario.nodes {
         if let Some(mobility) = &node.mobility {
-            node.position = mobility.update_position(node.position, 0.1);
+            node.position = mobility.update_position(node.position, 0.1, rng);
         }
     }
 }
@@ -458,7 +1235,14 @@ async fn collect_latency_measurements(scenario: &TestScenario, measurements: &mu
     }
 }
 
-async fn analyze_results(scenario: &TestScenario, sent: usize, received: usize, measurements: Vec<LatencyMeasurement>) -> TestExecutionResult {
+async fn analyze_results(
+    scenario: &TestScenario,
+    sent: usize,
+    received: usize,
+    measurements: Vec<LatencyMeasurement>,
+    total_hops: u64,
+    total_copies: usize,
+) -> TestExecutionResult {
     let delivery_ratio = if sent > 0 { received as f64 / sent as f64 } else { 0.0 };
     
     let avg_latency = if !measurements.is_empty() {
@@ -500,6 +1284,13 @@ async fn analyze_results(scenario: &TestScenario, sent: usize, received: usize,
             0.0
         },
         execution_time: Duration::from_secs(30),
+        seed: scenario.seed,
+        avg_hop_count: if received > 0 { total_hops as f64 / received as f64 } else { 0.0 },
+        copy_overhead_ratio: if total_copies > 0 {
+            total_copies.saturating_sub(received) as f64 / total_copies as f64
+        } else {
+            0.0
+        },
     }
 }
 
@@ -516,6 +1307,14 @@ pub struct TestExecutionResult {
     pub throughput_bps: f64,
     pub overhead_ratio: f64,
     pub execution_time: Duration,
+    /// RNG seed the scenario ran with, so a failing run can be replayed bit-for-bit.
+    pub seed: u64,
+    /// Average over delivered bundles of the simulation steps each spent in flight, plus one for
+    /// the delivering hop. A step in which a bundle is carried without moving counts too, so this
+    /// is not the number of hops its path took.
+    pub avg_hop_count: f64,
+    /// Fraction of replicated copies that never reached the destination (store-and-forward overhead).
+    pub copy_overhead_ratio: f64,
 }
 
 #[derive(Debug, Clone)]
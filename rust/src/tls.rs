@@ -0,0 +1,241 @@
+//! Optional TLS for [`crate::cla::TcpCla`], selected via `TransportConfig::with_tls`.
+//!
+//! Built on `rustls`/`tokio-rustls` so an accepted or connected `TcpStream` can be wrapped
+//! transparently before the TCPCL session layer ever touches it.
+
+use crate::error::{BpError, BpResult};
+use crate::types::TransportConfig;
+use pin_project_lite::pin_project;
+use std::{
+    io,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tokio::{
+    io::{AsyncRead, AsyncWrite, ReadBuf},
+    net::TcpStream,
+};
+use tokio_rustls::{TlsAcceptor, TlsConnector, TlsStream};
+
+/// TLS parameters read out of a [`TransportConfig`]'s `parameters` map.
+pub struct TlsProfile {
+    pub cert_path: String,
+    pub key_path: String,
+    pub ca_path: Option<String>,
+    pub require_client_auth: bool,
+    /// SNI hostname a client presents, and verifies the peer's certificate against, instead
+    /// of the literal connect-address IP. Unset for the server side, which doesn't send SNI.
+    pub server_name: Option<String>,
+}
+
+impl TlsProfile {
+    /// Read a TLS profile out of `config.parameters`, or `None` if `tls` isn't set.
+    pub fn from_config(config: &TransportConfig) -> Option<Self> {
+        if config.parameters.get("tls").map(String::as_str) != Some("true") {
+            return None;
+        }
+        Some(Self {
+            cert_path: config.parameters.get("tls_cert_path").cloned().unwrap_or_default(),
+            key_path: config.parameters.get("tls_key_path").cloned().unwrap_or_default(),
+            ca_path: config.parameters.get("tls_ca_path").cloned(),
+            require_client_auth: config.parameters.get("tls_client_auth").map(String::as_str) == Some("true"),
+            server_name: config.parameters.get("tls_server_name").cloned(),
+        })
+    }
+}
+
+fn load_certs(path: &str) -> BpResult<Vec<rustls::Certificate>> {
+    let file = std::fs::read(path).map_err(|e| BpError::TlsHandshake(format!("reading cert {}: {}", path, e)))?;
+    let mut reader = io::BufReader::new(&file[..]);
+    rustls_pemfile::certs(&mut reader)
+        .map_err(|e| BpError::TlsHandshake(format!("parsing cert {}: {}", path, e)))
+        .map(|certs| certs.into_iter().map(rustls::Certificate).collect())
+}
+
+fn load_key(path: &str) -> BpResult<rustls::PrivateKey> {
+    let file = std::fs::read(path).map_err(|e| BpError::TlsHandshake(format!("reading key {}: {}", path, e)))?;
+    let mut reader = io::BufReader::new(&file[..]);
+    let keys = rustls_pemfile::pkcs8_private_keys(&mut reader)
+        .map_err(|e| BpError::TlsHandshake(format!("parsing key {}: {}", path, e)))?;
+    keys.into_iter()
+        .next()
+        .map(rustls::PrivateKey)
+        .ok_or_else(|| BpError::TlsHandshake(format!("no private key found in {}", path)))
+}
+
+/// Build the raw `rustls` server config this profile describes, presenting `cert_path`/
+/// `key_path` and optionally requiring a client certificate signed by `ca_path`. Split out
+/// from [`build_acceptor`] so [`crate::quic::QuicCla`] can hand the same config to quinn
+/// instead of wrapping it in a `tokio_rustls::TlsAcceptor`.
+pub fn build_server_crypto(profile: &TlsProfile) -> BpResult<rustls::ServerConfig> {
+    let certs = load_certs(&profile.cert_path)?;
+    let key = load_key(&profile.key_path)?;
+
+    let builder = rustls::ServerConfig::builder().with_safe_defaults();
+
+    if profile.require_client_auth {
+        let ca_path = profile
+            .ca_path
+            .as_ref()
+            .ok_or_else(|| BpError::TlsHandshake("mutual TLS requires tls_ca_path".to_string()))?;
+        let mut roots = rustls::RootCertStore::empty();
+        for cert in load_certs(ca_path)? {
+            roots
+                .add(&cert)
+                .map_err(|e| BpError::TlsHandshake(format!("invalid client CA: {}", e)))?;
+        }
+        let verifier = rustls::server::AllowAnyAuthenticatedClient::new(roots);
+        builder
+            .with_client_cert_verifier(Arc::new(verifier))
+            .with_single_cert(certs, key)
+            .map_err(|e| BpError::TlsHandshake(format!("invalid server cert/key: {}", e)))
+    } else {
+        builder
+            .with_no_client_auth()
+            .with_single_cert(certs, key)
+            .map_err(|e| BpError::TlsHandshake(format!("invalid server cert/key: {}", e)))
+    }
+}
+
+/// Build a server-side TLS acceptor from this profile, presenting `cert_path`/`key_path` and
+/// optionally requiring a client certificate signed by `ca_path`.
+pub fn build_acceptor(profile: &TlsProfile) -> BpResult<TlsAcceptor> {
+    Ok(TlsAcceptor::from(Arc::new(build_server_crypto(profile)?)))
+}
+
+/// Build the raw `rustls` client config this profile describes, verifying the peer against
+/// `ca_path` (falling back to the bundled `webpki-roots` set) and presenting a client
+/// certificate if mutual TLS is required. Split out from [`build_connector`] so
+/// [`crate::quic::QuicCla`] can hand the same config to quinn instead of wrapping it in a
+/// `tokio_rustls::TlsConnector`.
+pub fn build_client_crypto(profile: &TlsProfile) -> BpResult<rustls::ClientConfig> {
+    let mut roots = rustls::RootCertStore::empty();
+    if let Some(ca_path) = &profile.ca_path {
+        for cert in load_certs(ca_path)? {
+            roots
+                .add(&cert)
+                .map_err(|e| BpError::TlsHandshake(format!("invalid CA cert: {}", e)))?;
+        }
+    } else {
+        roots.add_trust_anchors(webpki_roots::TLS_SERVER_ROOTS.0.iter().map(|ta| {
+            rustls::OwnedTrustAnchor::from_subject_spki_name_constraints(
+                ta.subject,
+                ta.spki,
+                ta.name_constraints,
+            )
+        }));
+    }
+
+    let builder = rustls::ClientConfig::builder().with_safe_defaults().with_root_certificates(roots);
+
+    if profile.require_client_auth {
+        let certs = load_certs(&profile.cert_path)?;
+        let key = load_key(&profile.key_path)?;
+        builder
+            .with_client_auth_cert(certs, key)
+            .map_err(|e| BpError::TlsHandshake(format!("invalid client cert/key: {}", e)))
+    } else {
+        Ok(builder.with_no_client_auth())
+    }
+}
+
+/// Build a client-side TLS connector from this profile, verifying the peer against
+/// `ca_path` (falling back to the bundled `webpki-roots` set) and presenting a client
+/// certificate if mutual TLS is required.
+pub fn build_connector(profile: &TlsProfile) -> BpResult<TlsConnector> {
+    Ok(TlsConnector::from(Arc::new(build_client_crypto(profile)?)))
+}
+
+pin_project! {
+    /// Either a plain `TcpStream` or one wrapped in TLS, so `TcpClSession` can drive either
+    /// without caring which.
+    #[project = MaybeTlsStreamProj]
+    pub enum MaybeTlsStream {
+        Plain { #[pin] inner: TcpStream },
+        Tls { #[pin] inner: TlsStream<TcpStream> },
+    }
+}
+
+impl MaybeTlsStream {
+    pub fn plain(stream: TcpStream) -> Self {
+        Self::Plain { inner: stream }
+    }
+
+    pub fn tls(stream: TlsStream<TcpStream>) -> Self {
+        Self::Tls { inner: stream }
+    }
+
+    /// The peer's leaf certificate (DER-encoded), if this is a TLS-wrapped connection and
+    /// the peer presented one during the handshake (always true for the server side under
+    /// normal TLS; only present for the client side under mutual TLS). `None` for a plain
+    /// connection.
+    pub fn peer_certificate(&self) -> Option<rustls::Certificate> {
+        match self {
+            Self::Plain { .. } => None,
+            Self::Tls { inner: TlsStream::Client(stream) } => {
+                stream.get_ref().1.peer_certificates()?.first().cloned()
+            }
+            Self::Tls { inner: TlsStream::Server(stream) } => {
+                stream.get_ref().1.peer_certificates()?.first().cloned()
+            }
+        }
+    }
+}
+
+impl AsyncRead for MaybeTlsStream {
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<io::Result<()>> {
+        match self.project() {
+            MaybeTlsStreamProj::Plain { inner } => inner.poll_read(cx, buf),
+            MaybeTlsStreamProj::Tls { inner } => inner.poll_read(cx, buf),
+        }
+    }
+}
+
+impl AsyncWrite for MaybeTlsStream {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        match self.project() {
+            MaybeTlsStreamProj::Plain { inner } => inner.poll_write(cx, buf),
+            MaybeTlsStreamProj::Tls { inner } => inner.poll_write(cx, buf),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.project() {
+            MaybeTlsStreamProj::Plain { inner } => inner.poll_flush(cx),
+            MaybeTlsStreamProj::Tls { inner } => inner.poll_flush(cx),
+        }
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.project() {
+            MaybeTlsStreamProj::Plain { inner } => inner.poll_shutdown(cx),
+            MaybeTlsStreamProj::Tls { inner } => inner.poll_shutdown(cx),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_absent_when_tls_unset() {
+        let config = TransportConfig::tcp("127.0.0.1:4556");
+        assert!(TlsProfile::from_config(&config).is_none());
+    }
+
+    #[test]
+    fn test_profile_parsed_from_parameters() {
+        let config = TransportConfig::tcp("127.0.0.1:4556")
+            .with_tls("cert.pem", "key.pem")
+            .with_tls_ca("ca.pem")
+            .with_mutual_tls();
+
+        let profile = TlsProfile::from_config(&config).unwrap();
+        assert_eq!(profile.cert_path, "cert.pem");
+        assert_eq!(profile.key_path, "key.pem");
+        assert_eq!(profile.ca_path.as_deref(), Some("ca.pem"));
+        assert!(profile.require_client_auth);
+    }
+}
@@ -1,10 +1,52 @@
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+
 use bytes::Bytes;
+#[cfg(feature = "std")]
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "std")]
 use std::collections::HashMap;
+#[cfg(not(feature = "std"))]
+use alloc::collections::BTreeMap;
+#[cfg(feature = "std")]
 use std::time::Duration;
+#[cfg(not(feature = "std"))]
+use core::time::Duration;
 use uuid::Uuid;
 
+/// Wall-clock source for the core data types, so `no_std` targets (which have no
+/// `std::time::SystemTime`) can supply their own notion of "now" instead of the crate hardcoding
+/// one. [`SystemClock`] is the `std`-backed default the crate's non-test code uses.
+pub trait Clock {
+    /// Milliseconds since the Unix epoch.
+    fn now_millis(&self) -> u64;
+}
+
+/// [`Clock`] backed by `std::time::SystemTime`, used by the `std`-gated `BpTimestamp::now`,
+/// `Bundle::new` and `Bundle::is_expired`. The zero-argument `Route`/`Contact`/`Range`/
+/// `Statistics` helpers read `Utc::now()` instead.
+#[cfg(feature = "std")]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+#[cfg(feature = "std")]
+impl Clock for SystemClock {
+    fn now_millis(&self) -> u64 {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64
+    }
+}
+
+/// String-keyed metadata map attached to a [`Bundle`]/[`Route`]. `HashMap` under the `std`
+/// feature (the default); `BTreeMap` over `alloc` otherwise, since `no_std` has no hasher source.
+#[cfg(feature = "std")]
+pub type MetadataMap = HashMap<String, String>;
+#[cfg(not(feature = "std"))]
+pub type MetadataMap = BTreeMap<String, String>;
+
 /// Bundle Priority levels
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Priority {
@@ -19,6 +61,16 @@ impl Default for Priority {
     }
 }
 
+impl Priority {
+    fn from_control_flags(flags: u64) -> Self {
+        match (flags >> 7) & 0b11 {
+            0 => Self::Bulk,
+            2 => Self::Expedited,
+            _ => Self::Standard,
+        }
+    }
+}
+
 /// Custody transfer options
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Custody {
@@ -33,6 +85,16 @@ impl Default for Custody {
     }
 }
 
+impl Custody {
+    fn from_control_flags(flags: u64) -> Self {
+        if flags & (1 << 3) != 0 {
+            Self::Required
+        } else {
+            Self::None
+        }
+    }
+}
+
 /// Bundle timestamp with microsecond precision
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct BpTimestamp {
@@ -41,17 +103,21 @@ pub struct BpTimestamp {
 }
 
 impl BpTimestamp {
-    pub fn now() -> Self {
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default();
-        
+    /// Build a timestamp from any [`Clock`], the `no_std`-safe entry point `now()` wraps with
+    /// [`SystemClock`].
+    pub fn from_clock(clock: &impl Clock) -> Self {
         Self {
-            msec: now.as_millis() as u64,
+            msec: clock.now_millis(),
             count: 0,
         }
     }
-    
+
+    #[cfg(feature = "std")]
+    pub fn now() -> Self {
+        Self::from_clock(&SystemClock)
+    }
+
+    #[cfg(feature = "std")]
     pub fn to_datetime(&self) -> DateTime<Utc> {
         DateTime::from_timestamp(self.msec as i64 / 1000, 0)
             .unwrap_or_else(|| Utc::now())
@@ -63,26 +129,90 @@ impl BpTimestamp {
 pub struct Eid(String);
 
 impl Eid {
+    /// Parse either an `ipn:NODE.SERVICE` or a `dtn:` URI-scheme EID (`dtn://node/demux`, or
+    /// the null endpoint `dtn:none`).
     pub fn new(eid: impl Into<String>) -> crate::error::BpResult<Self> {
         let eid = eid.into();
-        
-        if eid.starts_with("ipn:") && eid.contains('.') {
+
+        if Self::parse_ipn(&eid).is_some() || Self::parse_dtn(&eid).is_some() {
             Ok(Self(eid))
         } else {
             Err(crate::error::BpError::InvalidArgs)
         }
     }
-    
+
+    /// Build an `ipn:NODE.SERVICE` EID directly, without round-tripping through `new`'s parser.
+    pub fn ipn(node: u64, service: u64) -> Self {
+        Self(format!("ipn:{}.{}", node, service))
+    }
+
+    /// Build a `dtn://node/demux` EID directly, without round-tripping through `new`'s parser.
+    pub fn dtn(node: impl AsRef<str>, demux: impl AsRef<str>) -> Self {
+        Self(format!("dtn://{}/{}", node.as_ref(), demux.as_ref()))
+    }
+
+    /// The null endpoint, `dtn:none`: the conventional "nowhere" destination for bundles that
+    /// don't want status reports, e.g. as a default `report_to_eid`.
+    pub fn dtn_none() -> Self {
+        Self("dtn:none".to_string())
+    }
+
     pub fn as_str(&self) -> &str {
         &self.0
     }
-    
+
+    /// `"ipn"` or `"dtn"`, whichever scheme this EID was constructed with.
+    pub fn scheme(&self) -> &str {
+        if self.0.starts_with("ipn:") { "ipn" } else { "dtn" }
+    }
+
+    /// Whether this is the `dtn:none` null endpoint.
+    pub fn is_null(&self) -> bool {
+        self.0 == "dtn:none"
+    }
+
+    /// The demux part of a `dtn://node/demux` EID (the empty string if there is none), or
+    /// `None` if this isn't a `dtn://` endpoint (e.g. it's `ipn:` or the null endpoint).
+    pub fn demux(&self) -> Option<&str> {
+        match Self::parse_dtn(&self.0)? {
+            Some((_, demux_start)) => Some(&self.0[demux_start..]),
+            None => None,
+        }
+    }
+
     pub fn node_number(&self) -> Option<u64> {
-        self.0.strip_prefix("ipn:")?.split('.').next()?.parse().ok()
+        Self::parse_ipn(&self.0).map(|(node, _)| node)
     }
-    
+
     pub fn service_number(&self) -> Option<u64> {
-        self.0.strip_prefix("ipn:")?.split('.').nth(1)?.parse().ok()
+        Self::parse_ipn(&self.0).map(|(_, service)| service)
+    }
+
+    fn parse_ipn(s: &str) -> Option<(u64, u64)> {
+        let rest = s.strip_prefix("ipn:")?;
+        let mut parts = rest.splitn(2, '.');
+        let node: u64 = parts.next()?.parse().ok()?;
+        let service: u64 = parts.next()?.parse().ok()?;
+        Some((node, service))
+    }
+
+    /// `Some(None)` for the null endpoint `dtn:none`; `Some(Some((node, demux_start)))` for
+    /// `dtn://node/demux`, where `demux_start` is the byte offset the demux part starts at (so
+    /// callers needing the node name and ones needing the demux can each slice `s` without
+    /// re-parsing); `None` if `s` isn't a `dtn:` EID at all.
+    fn parse_dtn(s: &str) -> Option<Option<(String, usize)>> {
+        let rest = s.strip_prefix("dtn:")?;
+        if rest == "none" {
+            return Some(None);
+        }
+        let rest = rest.strip_prefix("//")?;
+        let prefix_len = s.len() - rest.len();
+        let (node, _) = rest.split_once('/').unwrap_or((rest, ""));
+        if node.is_empty() {
+            return None;
+        }
+        let demux_start = prefix_len + node.len() + 1;
+        Some(Some((node.to_string(), demux_start.min(s.len()))))
     }
 }
 
@@ -112,24 +242,32 @@ pub struct Bundle {
     pub priority: Priority,
     pub custody: Custody,
     pub payload: Bytes,
-    pub metadata: HashMap<String, String>,
+    pub metadata: MetadataMap,
 }
 
 impl Bundle {
-    pub fn new(source_eid: Eid, dest_eid: Eid, payload: impl Into<Bytes>) -> Self {
+    /// Build a bundle, stamping `creation_time` from `clock` rather than assuming a
+    /// `std::time::SystemTime` is available. `no_std` callers use this directly; `new` is `std`
+    /// sugar over it via [`SystemClock`].
+    pub fn with_clock(source_eid: Eid, dest_eid: Eid, payload: impl Into<Bytes>, clock: &impl Clock) -> Self {
         Self {
             id: Uuid::new_v4(),
             source_eid,
             dest_eid,
             report_to_eid: None,
-            creation_time: BpTimestamp::now(),
+            creation_time: BpTimestamp::from_clock(clock),
             ttl: Duration::from_secs(3600),
             priority: Priority::default(),
             custody: Custody::default(),
             payload: payload.into(),
-            metadata: HashMap::new(),
+            metadata: MetadataMap::new(),
         }
     }
+
+    #[cfg(feature = "std")]
+    pub fn new(source_eid: Eid, dest_eid: Eid, payload: impl Into<Bytes>) -> Self {
+        Self::with_clock(source_eid, dest_eid, payload, &SystemClock)
+    }
     
     pub fn with_priority(mut self, priority: Priority) -> Self {
         self.priority = priority;
@@ -160,12 +298,117 @@ impl Bundle {
         self.payload.len()
     }
     
-    pub fn is_expired(&self) -> bool {
-        let elapsed = BpTimestamp::now().msec.saturating_sub(self.creation_time.msec);
+    /// Whether this bundle's TTL has elapsed as of `now_millis` (Unix epoch milliseconds).
+    pub fn is_expired_at(&self, now_millis: u64) -> bool {
+        let elapsed = now_millis.saturating_sub(self.creation_time.msec);
         elapsed > self.ttl.as_millis() as u64
     }
+
+    #[cfg(feature = "std")]
+    pub fn is_expired(&self) -> bool {
+        self.is_expired_at(SystemClock.now_millis())
+    }
+
+    /// Encode this bundle in canonical CBOR (primary block + payload block, definite-length
+    /// arrays and shortest-form integers throughout), with EIDs and timestamps laid out as in
+    /// BPv7. Both blocks name CRC type 2 but carry no CRC value of their own; one CRC-32C over
+    /// the whole encoding is appended after it instead, so the bytes are read back by
+    /// [`Bundle::from_cbor`] and not by an RFC 9171 decoder.
+    pub fn to_cbor(&self) -> crate::error::BpResult<Bytes> {
+        let mut out = Vec::new();
+        cbor::write_array_header(&mut out, 2);
+
+        cbor::write_array_header(&mut out, 8);
+        cbor::write_uint(&mut out, 7); // BPv7 version
+        cbor::write_uint(&mut out, self.control_flags());
+        cbor::write_uint(&mut out, 2); // primary block CRC type: CRC-32C
+        cbor::write_eid(&mut out, &self.dest_eid)?;
+        cbor::write_eid(&mut out, &self.source_eid)?;
+        cbor::write_eid(&mut out, self.report_to_eid.as_ref().unwrap_or(&self.source_eid))?;
+        cbor::write_array_header(&mut out, 2);
+        cbor::write_uint(&mut out, self.creation_time.msec);
+        cbor::write_uint(&mut out, self.creation_time.count as u64);
+        cbor::write_uint(&mut out, self.ttl.as_millis() as u64);
+
+        cbor::write_array_header(&mut out, 5);
+        cbor::write_uint(&mut out, 1); // block type: payload
+        cbor::write_uint(&mut out, 1); // block number
+        cbor::write_uint(&mut out, 0); // block processing flags
+        cbor::write_uint(&mut out, 2); // payload block CRC type: CRC-32C
+        cbor::write_bytes(&mut out, &self.payload);
+
+        out.extend_from_slice(&cbor::crc32c(&out).to_be_bytes());
+        Ok(Bytes::from(out))
+    }
+
+    /// Decode a bundle produced by [`Bundle::to_cbor`], verifying the trailing CRC-32C first.
+    /// Only that layout is accepted: a definite-length two-block array with no per-block CRC
+    /// values, followed by the CRC.
+    pub fn from_cbor(data: &[u8]) -> crate::error::BpResult<Self> {
+        if data.len() < 4 {
+            return Err(crate::error::BpError::Protocol("CBOR bundle too short".to_string()));
+        }
+        let (body, crc_bytes) = data.split_at(data.len() - 4);
+        if cbor::crc32c(body) != u32::from_be_bytes(crc_bytes.try_into().unwrap()) {
+            return Err(crate::error::BpError::Protocol("CBOR bundle CRC mismatch".to_string()));
+        }
+
+        let mut reader = cbor::Reader::new(body);
+        reader.expect_array_len(2)?;
+
+        reader.expect_array_len(8)?;
+        let _version = reader.read_uint()?;
+        let control_flags = reader.read_uint()?;
+        let _primary_crc_type = reader.read_uint()?;
+        let dest_eid = reader.read_eid()?;
+        let source_eid = reader.read_eid()?;
+        let report_to_eid = reader.read_eid()?;
+        reader.expect_array_len(2)?;
+        let msec = reader.read_uint()?;
+        let count = reader.read_uint()? as u32;
+        let lifetime_ms = reader.read_uint()?;
+
+        reader.expect_array_len(5)?;
+        let _block_type = reader.read_uint()?;
+        let _block_number = reader.read_uint()?;
+        let _block_flags = reader.read_uint()?;
+        let _payload_crc_type = reader.read_uint()?;
+        let payload = reader.read_bytes()?;
+
+        Ok(Self {
+            id: Uuid::new_v4(),
+            source_eid,
+            dest_eid,
+            report_to_eid: Some(report_to_eid),
+            creation_time: BpTimestamp { msec, count },
+            ttl: Duration::from_millis(lifetime_ms),
+            priority: Priority::from_control_flags(control_flags),
+            custody: Custody::from_control_flags(control_flags),
+            payload: Bytes::copy_from_slice(payload),
+            metadata: MetadataMap::new(),
+        })
+    }
+
+    fn control_flags(&self) -> u64 {
+        let priority_bits = match self.priority {
+            Priority::Bulk => 0u64,
+            Priority::Standard => 1,
+            Priority::Expedited => 2,
+        };
+        let custody_bit = if self.custody != Custody::None { 1u64 << 3 } else { 0 };
+        (priority_bits << 7) | custody_bit
+    }
 }
 
+/// Wall-clock instant used by [`Route`], [`Contact`], and [`Range`]: `chrono::DateTime<Utc>`
+/// under the `std` feature, which every zero-argument `is_valid`/`is_active` convenience below
+/// assumes; milliseconds since the Unix epoch over `alloc` otherwise, since `Utc::now()` needs
+/// `std`. The `_at`-suffixed methods take one as an explicit argument and work under either.
+#[cfg(feature = "std")]
+pub type WallClockTime = DateTime<Utc>;
+#[cfg(not(feature = "std"))]
+pub type WallClockTime = u64;
+
 /// Route information for routing algorithms
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Route {
@@ -173,34 +416,46 @@ pub struct Route {
     pub next_hop: Eid,
     pub cost: u32,
     pub confidence: f32,
-    pub valid_until: DateTime<Utc>,
-    pub metadata: HashMap<String, String>,
+    pub valid_until: WallClockTime,
+    pub metadata: MetadataMap,
 }
 
 impl Route {
-    pub fn new(dest_eid: Eid, next_hop: Eid, cost: u32) -> Self {
+    /// Build a route valid until `valid_until`, the `no_std`-safe entry point `new` is `std`
+    /// sugar over (defaulting `valid_until` to one hour from now).
+    pub fn new_at(dest_eid: Eid, next_hop: Eid, cost: u32, valid_until: WallClockTime) -> Self {
         Self {
             dest_eid,
             next_hop,
             cost,
             confidence: 1.0,
-            valid_until: Utc::now() + chrono::Duration::hours(1),
-            metadata: HashMap::new(),
+            valid_until,
+            metadata: MetadataMap::new(),
         }
     }
-    
+
+    #[cfg(feature = "std")]
+    pub fn new(dest_eid: Eid, next_hop: Eid, cost: u32) -> Self {
+        Self::new_at(dest_eid, next_hop, cost, Utc::now() + chrono::Duration::hours(1))
+    }
+
     pub fn with_confidence(mut self, confidence: f32) -> Self {
         self.confidence = confidence.clamp(0.0, 1.0);
         self
     }
-    
-    pub fn with_validity(mut self, valid_until: DateTime<Utc>) -> Self {
+
+    pub fn with_validity(mut self, valid_until: WallClockTime) -> Self {
         self.valid_until = valid_until;
         self
     }
-    
+
+    pub fn is_valid_at(&self, now: WallClockTime) -> bool {
+        now < self.valid_until
+    }
+
+    #[cfg(feature = "std")]
     pub fn is_valid(&self) -> bool {
-        Utc::now() < self.valid_until
+        self.is_valid_at(Utc::now())
     }
 }
 
@@ -208,17 +463,20 @@ impl Route {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Contact {
     pub neighbor_eid: Eid,
-    pub start_time: DateTime<Utc>,
-    pub end_time: DateTime<Utc>,
+    pub start_time: WallClockTime,
+    pub end_time: WallClockTime,
     pub data_rate: u32, // bits per second
     pub confidence: f32,
+    /// The node this contact originates from, for contact plans spanning more than one hop.
+    /// `None` means this is one of the local node's own contacts.
+    pub from_eid: Option<Eid>,
 }
 
 impl Contact {
     pub fn new(
         neighbor_eid: Eid,
-        start_time: DateTime<Utc>,
-        end_time: DateTime<Utc>,
+        start_time: WallClockTime,
+        end_time: WallClockTime,
         data_rate: u32,
     ) -> Self {
         Self {
@@ -227,33 +485,49 @@ impl Contact {
             end_time,
             data_rate,
             confidence: 1.0,
+            from_eid: None,
         }
     }
-    
+
+    pub fn with_confidence(mut self, confidence: f32) -> Self {
+        self.confidence = confidence.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn with_from_eid(mut self, from_eid: Eid) -> Self {
+        self.from_eid = Some(from_eid);
+        self
+    }
+
+    #[cfg(feature = "std")]
     pub fn duration(&self) -> chrono::Duration {
         self.end_time - self.start_time
     }
-    
-    pub fn is_active(&self) -> bool {
-        let now = Utc::now();
+
+    pub fn is_active_at(&self, now: WallClockTime) -> bool {
         now >= self.start_time && now <= self.end_time
     }
+
+    #[cfg(feature = "std")]
+    pub fn is_active(&self) -> bool {
+        self.is_active_at(Utc::now())
+    }
 }
 
 /// Range information (One-Way Light Time)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Range {
     pub neighbor_eid: Eid,
-    pub start_time: DateTime<Utc>,
-    pub end_time: DateTime<Utc>,
+    pub start_time: WallClockTime,
+    pub end_time: WallClockTime,
     pub owlt: Duration, // One-Way Light Time
 }
 
 impl Range {
     pub fn new(
         neighbor_eid: Eid,
-        start_time: DateTime<Utc>,
-        end_time: DateTime<Utc>,
+        start_time: WallClockTime,
+        end_time: WallClockTime,
         owlt: Duration,
     ) -> Self {
         Self {
@@ -263,11 +537,15 @@ impl Range {
             owlt,
         }
     }
-    
-    pub fn is_valid(&self) -> bool {
-        let now = Utc::now();
+
+    pub fn is_valid_at(&self, now: WallClockTime) -> bool {
         now >= self.start_time && now <= self.end_time
     }
+
+    #[cfg(feature = "std")]
+    pub fn is_valid(&self) -> bool {
+        self.is_valid_at(Utc::now())
+    }
 }
 
 /// CLA transport configuration
@@ -279,6 +557,30 @@ pub struct TransportConfig {
     pub max_payload_size: usize,
     pub data_rate: u32,
     pub parameters: HashMap<String, String>,
+    /// Wire format CLAs use to (de)serialize bundles; see [`crate::codec::BundleCodec`].
+    /// Defaults to `"json"` to match the SDK's original behavior.
+    pub codec: String,
+    /// Delay before the first reconnect attempt after a broken connection.
+    pub reconnect_initial_backoff: Duration,
+    /// Multiplier applied to the backoff delay after each failed attempt.
+    pub reconnect_backoff_factor: f64,
+    /// Reconnect backoff ceiling.
+    pub reconnect_max_backoff: Duration,
+    /// Reconnect attempts before giving up and returning a `BpError`.
+    pub reconnect_max_attempts: u32,
+    /// Keepalive interval (seconds) this side proposes during TCPCL contact negotiation;
+    /// the session adopts `min(local, peer)` once both sides have advertised theirs.
+    pub keepalive_interval: u16,
+    /// Idle timeout (seconds) this side proposes during TCPCL contact negotiation, after
+    /// which a session with no traffic or keepalive is torn down; negotiated the same way
+    /// as `keepalive_interval`.
+    pub peer_timeout: u16,
+    /// Maximum number of outbound connections `TcpCla`'s write pool holds at once; past
+    /// this, the least-recently-used pooled connection is evicted to make room.
+    pub pool_max_size: usize,
+    /// How long an outbound pooled connection may sit unused before `TcpCla` evicts it
+    /// rather than handing it to the next send.
+    pub pool_idle_timeout: Duration,
 }
 
 impl TransportConfig {
@@ -290,9 +592,18 @@ impl TransportConfig {
             max_payload_size: 65536,
             data_rate: 1_000_000,
             parameters: HashMap::new(),
+            codec: "json".to_string(),
+            reconnect_initial_backoff: Duration::from_millis(100),
+            reconnect_backoff_factor: 2.0,
+            reconnect_max_backoff: Duration::from_secs(30),
+            reconnect_max_attempts: 5,
+            keepalive_interval: 30,
+            peer_timeout: 90,
+            pool_max_size: 64,
+            pool_idle_timeout: Duration::from_secs(300),
         }
     }
-    
+
     pub fn udp(local_address: impl Into<String>) -> Self {
         Self {
             protocol: "udp".to_string(),
@@ -301,8 +612,121 @@ impl TransportConfig {
             max_payload_size: 1472,
             data_rate: 1_000_000,
             parameters: HashMap::new(),
+            codec: "json".to_string(),
+            reconnect_initial_backoff: Duration::from_millis(100),
+            reconnect_backoff_factor: 2.0,
+            reconnect_max_backoff: Duration::from_secs(30),
+            reconnect_max_attempts: 5,
+            keepalive_interval: 30,
+            peer_timeout: 90,
+            pool_max_size: 64,
+            pool_idle_timeout: Duration::from_secs(300),
         }
     }
+
+    /// Config for a TLS-secured `TcpCla` registered under protocol name `"tls"` rather than
+    /// `"tcp"`. Functionally identical to [`Self::tcp`] plus `with_tls`/`with_mutual_tls`,
+    /// which `TcpCla::with_resolver` requires to be set for this protocol name.
+    pub fn tls(local_address: impl Into<String>) -> Self {
+        Self { protocol: "tls".to_string(), ..Self::tcp(local_address) }
+    }
+
+    /// Config for `QuicCla`. QUIC is always encrypted, so unlike [`Self::tcp`] this expects
+    /// `with_tls`/`with_mutual_tls` to be set before the CLA is constructed.
+    pub fn quic(local_address: impl Into<String>) -> Self {
+        Self {
+            protocol: "quic".to_string(),
+            local_address: local_address.into(),
+            remote_address: None,
+            max_payload_size: 65536,
+            data_rate: 1_000_000,
+            parameters: HashMap::new(),
+            codec: "json".to_string(),
+            reconnect_initial_backoff: Duration::from_millis(100),
+            reconnect_backoff_factor: 2.0,
+            reconnect_max_backoff: Duration::from_secs(30),
+            reconnect_max_attempts: 5,
+            keepalive_interval: 30,
+            peer_timeout: 90,
+            pool_max_size: 64,
+            pool_idle_timeout: Duration::from_secs(300),
+        }
+    }
+
+    /// Config for `UnixCla`. `local_address` is a filesystem path for the listening socket
+    /// rather than a host/port; the reconnect/keepalive fields are unused since a Unix
+    /// socket has no DNS to re-resolve and no TCPCL handshake to negotiate a cadence for.
+    pub fn unix(local_address: impl Into<String>) -> Self {
+        Self { protocol: "unix".to_string(), ..Self::tcp(local_address) }
+    }
+
+    /// Tune the reconnect backoff policy used by connection-oriented CLAs like `TcpCla`.
+    pub fn with_backoff(mut self, initial: Duration, factor: f64, max: Duration, max_attempts: u32) -> Self {
+        self.reconnect_initial_backoff = initial;
+        self.reconnect_backoff_factor = factor;
+        self.reconnect_max_backoff = max;
+        self.reconnect_max_attempts = max_attempts;
+        self
+    }
+
+    /// Propose a keepalive cadence and idle timeout (seconds) for TCPCL session
+    /// negotiation; the live session adopts the minimum of each value the two peers
+    /// advertise during the contact handshake.
+    pub fn with_keepalive(mut self, keepalive_interval: u16, peer_timeout: u16) -> Self {
+        self.keepalive_interval = keepalive_interval;
+        self.peer_timeout = peer_timeout;
+        self
+    }
+
+    /// Select the bundle codec this transport uses (e.g. `"json"`, `"cbor"`, `"bincode"`,
+    /// `"msgpack"`). See [`crate::codec::codec_for`] for the accepted names.
+    pub fn with_codec(mut self, codec: impl Into<String>) -> Self {
+        self.codec = codec.into();
+        self
+    }
+
+    /// Bound `TcpCla`'s outbound write pool at `max_size` live connections, evicting the
+    /// least-recently-used one once a new destination needs room.
+    pub fn with_pool_size(mut self, max_size: usize) -> Self {
+        self.pool_max_size = max_size;
+        self
+    }
+
+    /// Evict an outbound pooled connection after it sits unused for `idle_timeout`, so a
+    /// link to a peer that's gone away doesn't hold a dead socket open indefinitely.
+    pub fn with_pool_idle_timeout(mut self, idle_timeout: Duration) -> Self {
+        self.pool_idle_timeout = idle_timeout;
+        self
+    }
+
+    /// Enable TLS for this transport, presenting `cert_path`/`key_path` as the local
+    /// identity. Stored in `parameters` so `TcpCla` can pick it up without a dedicated field.
+    pub fn with_tls(mut self, cert_path: impl Into<String>, key_path: impl Into<String>) -> Self {
+        self.parameters.insert("tls".to_string(), "true".to_string());
+        self.parameters.insert("tls_cert_path".to_string(), cert_path.into());
+        self.parameters.insert("tls_key_path".to_string(), key_path.into());
+        self
+    }
+
+    /// Verify peer certificates against `ca_path` instead of the bundled `webpki-roots` set.
+    pub fn with_tls_ca(mut self, ca_path: impl Into<String>) -> Self {
+        self.parameters.insert("tls_ca_path".to_string(), ca_path.into());
+        self
+    }
+
+    /// Require and verify a client certificate on accepted connections (mutual TLS).
+    pub fn with_mutual_tls(mut self) -> Self {
+        self.parameters.insert("tls_client_auth".to_string(), "true".to_string());
+        self
+    }
+
+    /// Verify the peer's certificate against this hostname (sent as the TLS SNI) instead of
+    /// the connect address's literal IP. Needed whenever the certificate's subject doesn't
+    /// cover the IP a peer happens to be reached at.
+    pub fn with_tls_server_name(mut self, server_name: impl Into<String>) -> Self {
+        self.parameters.insert("tls_server_name".to_string(), server_name.into());
+        self
+    }
 }
 
 /// Statistics for monitoring
@@ -315,18 +739,199 @@ pub struct Statistics {
     pub bundles_deleted: u64,
     pub bytes_sent: u64,
     pub bytes_received: u64,
-    pub last_reset: DateTime<Utc>,
+    /// Custodial bundles whose custody-acceptance signal arrived before their retransmission
+    /// deadline.
+    pub custody_accepted: u64,
+    /// Times a custodial bundle was resent after its deadline elapsed with no signal.
+    pub custody_retransmitted: u64,
+    /// Custodial bundles given up on, either after exhausting retries or on an explicit
+    /// custody-refusal signal.
+    pub custody_failed: u64,
+    /// Times a CLA's background health check attempted to reconnect a dropped
+    /// listener/socket; see `Cla::connection_state`/`Cla::retry_count`.
+    pub reconnect_attempts: u64,
+    pub last_reset: WallClockTime,
 }
 
 impl Statistics {
-    pub fn new() -> Self {
+    /// Build zeroed statistics stamped with `now`, the `no_std`-safe entry point `new` is `std`
+    /// sugar over (via `Utc::now()`).
+    pub fn new_at(now: WallClockTime) -> Self {
         Self {
-            last_reset: Utc::now(),
+            last_reset: now,
             ..Default::default()
         }
     }
-    
+
+    #[cfg(feature = "std")]
+    pub fn new() -> Self {
+        Self::new_at(Utc::now())
+    }
+
+    pub fn reset_at(&mut self, now: WallClockTime) {
+        *self = Self::new_at(now);
+    }
+
+    #[cfg(feature = "std")]
     pub fn reset(&mut self) {
-        *self = Self::new();
+        self.reset_at(Utc::now());
+    }
+}
+/// Minimal RFC 8949 canonical-CBOR codec for BPv7 wire structures: just enough to encode and
+/// decode the primary/payload block arrays `Bundle::to_cbor`/`from_cbor` need, using
+/// definite-length arrays and shortest-form integers throughout.
+mod cbor {
+    use super::Eid;
+    use crate::error::{BpError, BpResult};
+
+    pub fn write_header(out: &mut Vec<u8>, major: u8, value: u64) {
+        let top = major << 5;
+        if value < 24 {
+            out.push(top | value as u8);
+        } else if value <= u8::MAX as u64 {
+            out.push(top | 24);
+            out.push(value as u8);
+        } else if value <= u16::MAX as u64 {
+            out.push(top | 25);
+            out.extend_from_slice(&(value as u16).to_be_bytes());
+        } else if value <= u32::MAX as u64 {
+            out.push(top | 26);
+            out.extend_from_slice(&(value as u32).to_be_bytes());
+        } else {
+            out.push(top | 27);
+            out.extend_from_slice(&value.to_be_bytes());
+        }
+    }
+
+    pub fn write_uint(out: &mut Vec<u8>, value: u64) {
+        write_header(out, 0, value);
+    }
+
+    pub fn write_array_header(out: &mut Vec<u8>, len: u64) {
+        write_header(out, 4, len);
+    }
+
+    pub fn write_bytes(out: &mut Vec<u8>, data: &[u8]) {
+        write_header(out, 2, data.len() as u64);
+        out.extend_from_slice(data);
+    }
+
+    pub fn write_text(out: &mut Vec<u8>, text: &str) {
+        write_header(out, 3, text.len() as u64);
+        out.extend_from_slice(text.as_bytes());
+    }
+
+    /// Encode an EID as `[2, [node, service]]` for `ipn:` or `[1, ssp]` for `dtn:`.
+    pub fn write_eid(out: &mut Vec<u8>, eid: &Eid) -> BpResult<()> {
+        let raw = eid.as_str();
+        write_array_header(out, 2);
+        if let Some(rest) = raw.strip_prefix("ipn:") {
+            let mut parts = rest.splitn(2, '.');
+            let node: u64 = parts.next().unwrap_or("0").parse().unwrap_or(0);
+            let service: u64 = parts.next().unwrap_or("0").parse().unwrap_or(0);
+            write_uint(out, 2);
+            write_array_header(out, 2);
+            write_uint(out, node);
+            write_uint(out, service);
+            Ok(())
+        } else if let Some(ssp) = raw.strip_prefix("dtn:") {
+            write_uint(out, 1);
+            write_text(out, ssp);
+            Ok(())
+        } else {
+            Err(BpError::Protocol(format!("Unsupported EID scheme for CBOR encoding: {}", raw)))
+        }
+    }
+
+    /// CRC-32C (Castagnoli), the checksum BPv7 primary/payload blocks use for CRC type 2.
+    pub fn crc32c(data: &[u8]) -> u32 {
+        let mut crc: u32 = 0xFFFF_FFFF;
+        for &byte in data {
+            crc ^= byte as u32;
+            for _ in 0..8 {
+                crc = if crc & 1 != 0 { (crc >> 1) ^ 0x82F6_3B78 } else { crc >> 1 };
+            }
+        }
+        !crc
+    }
+
+    pub struct Reader<'a> {
+        data: &'a [u8],
+        pos: usize,
     }
-} 
\ No newline at end of file
+
+    impl<'a> Reader<'a> {
+        pub fn new(data: &'a [u8]) -> Self {
+            Self { data, pos: 0 }
+        }
+
+        fn take(&mut self, len: usize) -> BpResult<&'a [u8]> {
+            let end = self.pos.checked_add(len).filter(|&end| end <= self.data.len())
+                .ok_or_else(|| BpError::Protocol("Unexpected end of CBOR data".to_string()))?;
+            let slice = &self.data[self.pos..end];
+            self.pos = end;
+            Ok(slice)
+        }
+
+        fn read_header(&mut self) -> BpResult<(u8, u64)> {
+            let byte = *self.take(1)?.first().unwrap();
+            let major = byte >> 5;
+            let value = match byte & 0x1f {
+                info @ 0..=23 => info as u64,
+                24 => self.take(1)?[0] as u64,
+                25 => u16::from_be_bytes(self.take(2)?.try_into().unwrap()) as u64,
+                26 => u32::from_be_bytes(self.take(4)?.try_into().unwrap()) as u64,
+                27 => u64::from_be_bytes(self.take(8)?.try_into().unwrap()),
+                _ => return Err(BpError::Protocol("Unsupported CBOR additional info".to_string())),
+            };
+            Ok((major, value))
+        }
+
+        pub fn read_uint(&mut self) -> BpResult<u64> {
+            match self.read_header()? {
+                (0, value) => Ok(value),
+                _ => Err(BpError::Protocol("Expected CBOR unsigned integer".to_string())),
+            }
+        }
+
+        pub fn expect_array_len(&mut self, expected: u64) -> BpResult<()> {
+            match self.read_header()? {
+                (4, len) if len == expected => Ok(()),
+                (4, len) => Err(BpError::Protocol(format!("Expected CBOR array of length {}, got {}", expected, len))),
+                _ => Err(BpError::Protocol("Expected CBOR array".to_string())),
+            }
+        }
+
+        pub fn read_bytes(&mut self) -> BpResult<&'a [u8]> {
+            match self.read_header()? {
+                (2, len) => self.take(len as usize),
+                _ => Err(BpError::Protocol("Expected CBOR byte string".to_string())),
+            }
+        }
+
+        pub fn read_text(&mut self) -> BpResult<&'a str> {
+            match self.read_header()? {
+                (3, len) => std::str::from_utf8(self.take(len as usize)?)
+                    .map_err(|_| BpError::Protocol("Invalid UTF-8 in CBOR text string".to_string())),
+                _ => Err(BpError::Protocol("Expected CBOR text string".to_string())),
+            }
+        }
+
+        pub fn read_eid(&mut self) -> BpResult<Eid> {
+            self.expect_array_len(2)?;
+            match self.read_uint()? {
+                2 => {
+                    self.expect_array_len(2)?;
+                    let node = self.read_uint()?;
+                    let service = self.read_uint()?;
+                    Eid::new(format!("ipn:{}.{}", node, service))
+                }
+                1 => {
+                    let ssp = self.read_text()?;
+                    Eid::new(format!("dtn:{}", ssp))
+                }
+                scheme => Err(BpError::Protocol(format!("Unknown EID scheme code: {}", scheme))),
+            }
+        }
+    }
+}
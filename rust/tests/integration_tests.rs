@@ -270,4 +270,27 @@ fn test_bundle_expiration() {
     let bundle = Bundle::new(source, dest, "test")
         .with_ttl(Duration::from_secs(3600));
     assert!(!bundle.is_expired());
-} 
\ No newline at end of file
+}
+
+#[tokio::test]
+async fn test_send_stream_with_custody_tracks_bundle() {
+    let node_eid = Eid::new("ipn:1.1").unwrap();
+    let sdk = BpSdk::new(node_eid.clone(), None).unwrap();
+    sdk.init().await.unwrap();
+
+    let dest = Eid::new("ipn:2.1").unwrap();
+    let bundle = Bundle::new(node_eid, dest, "")
+        .with_custody(Custody::Required);
+
+    let chunks = futures_util::stream::iter(vec![
+        bytes::Bytes::from_static(b"hello, "),
+        bytes::Bytes::from_static(b"streamed world"),
+    ]);
+
+    // The real payload comes from `chunks`, not `bundle.payload`; custody tracking must still
+    // pick it up for retransmission rather than the bundle's (empty) payload field.
+    sdk.send_stream(bundle, chunks).await.unwrap();
+    assert_eq!(sdk.custody_outstanding_count(), 1);
+
+    sdk.shutdown().await.unwrap();
+}
\ No newline at end of file